@@ -1,46 +1,259 @@
+use crate::inline::{LinkRefMap, LinkReference, normalize_reference_label};
+use std::collections::BTreeMap;
+
+/// HTML attributes from a `{#id .class key=value}` block, parsed with
+/// [`ParseOptions::enable_attributes`](crate::ParseOptions::enable_attributes).
+/// Classes are joined into one space-separated `class` value.
+pub type Attributes = BTreeMap<String, String>;
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Block {
     Document {
         children: Vec<Block>,
+        pos: Option<SourcePos>,
     },
     BlockQuote {
         children: Vec<Block>,
+        pos: Option<SourcePos>,
+    },
+    /// A GitHub alert: a top-level block quote whose first line is `[!NOTE]`,
+    /// `[!TIP]`, `[!IMPORTANT]`, `[!WARNING]` or `[!CAUTION]`. `children` do
+    /// not include the marker line.
+    Alert {
+        kind: AlertKind,
+        children: Vec<Block>,
+        pos: Option<SourcePos>,
     },
     List {
         kind: ListKind,
         start: u32,
         tight: bool,
         children: Vec<Block>,
+        pos: Option<SourcePos>,
     },
     ListItem {
         children: Vec<Block>,
         checked: Option<bool>,
+        pos: Option<SourcePos>,
     },
     Paragraph {
         raw: String,
+        children: Vec<Inline>,
+        pos: Option<SourcePos>,
     },
     Heading {
         level: u8,
         raw: String,
+        children: Vec<Inline>,
+        /// The slug generated with
+        /// [`ParseOptions::heading_ids`](crate::ParseOptions::heading_ids).
+        /// Headings with an explicit `#id` in `attrs` use that instead and get
+        /// no slug.
+        id: Option<String>,
+        attrs: Attributes,
+        pos: Option<SourcePos>,
     },
     CodeBlock {
         info: String,
         literal: String,
+        /// Rendered on the `<pre>` element.
+        attrs: Attributes,
+        pos: Option<SourcePos>,
     },
     HtmlBlock {
         literal: String,
+        pos: Option<SourcePos>,
+    },
+    /// Display math from a `$$` block or a ```` ```math ```` fence. `literal`
+    /// is the raw TeX source without the delimiters.
+    MathBlock {
+        literal: String,
+        pos: Option<SourcePos>,
+    },
+    ThematicBreak {
+        pos: Option<SourcePos>,
     },
-    ThematicBreak,
     Table(Box<TableData>),
+    /// A `:::name attributes` … `:::` container from
+    /// [`enable_containers`](crate::ParseOptions::enable_containers).
+    Container {
+        name: String,
+        /// The rest of the opening line after the name, trimmed.
+        attributes: String,
+        children: Vec<Block>,
+        pos: Option<SourcePos>,
+    },
+    /// A definition list from
+    /// [`enable_definition_lists`](crate::ParseOptions::enable_definition_lists).
+    /// `children` are [`DefinitionTerm`](Block::DefinitionTerm)s, each followed
+    /// by one or more [`DefinitionDetails`](Block::DefinitionDetails).
+    DefinitionList {
+        tight: bool,
+        children: Vec<Block>,
+        pos: Option<SourcePos>,
+    },
+    /// The paragraph naming a term, rendered as `<dt>`.
+    DefinitionTerm {
+        raw: String,
+        children: Vec<Inline>,
+        pos: Option<SourcePos>,
+    },
+    /// A `: definition`, rendered as `<dd>`.
+    DefinitionDetails {
+        children: Vec<Block>,
+        pos: Option<SourcePos>,
+    },
+    /// A `[^label]: …` footnote definition. It is rendered in the footnotes
+    /// section at the end of the document, and only if referenced.
+    FootnoteDefinition {
+        /// The label as written, without `[^` and `]:`.
+        label: String,
+        children: Vec<Block>,
+        pos: Option<SourcePos>,
+    },
+}
+
+impl Block {
+    /// Source range of this block, if parsed with
+    /// [`ParseOptions::source_positions`](crate::ParseOptions::source_positions) enabled.
+    pub fn pos(&self) -> Option<SourcePos> {
+        match self {
+            Block::Document { pos, .. }
+            | Block::BlockQuote { pos, .. }
+            | Block::Alert { pos, .. }
+            | Block::List { pos, .. }
+            | Block::ListItem { pos, .. }
+            | Block::Paragraph { pos, .. }
+            | Block::Heading { pos, .. }
+            | Block::CodeBlock { pos, .. }
+            | Block::HtmlBlock { pos, .. }
+            | Block::MathBlock { pos, .. }
+            | Block::ThematicBreak { pos }
+            | Block::FootnoteDefinition { pos, .. }
+            | Block::Container { pos, .. }
+            | Block::DefinitionList { pos, .. }
+            | Block::DefinitionTerm { pos, .. }
+            | Block::DefinitionDetails { pos, .. } => *pos,
+            Block::Table(td) => td.pos,
+        }
+    }
+}
+
+/// A point in the source text. `line` and `column` are 1-based, `column`
+/// and `offset` count bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+/// Source range of a node. `end` points just past the last byte of the node,
+/// excluding trailing whitespace and line endings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourcePos {
+    pub start: Position,
+    pub end: Position,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableData {
     pub alignments: Vec<TableAlignment>,
-    pub header: Vec<String>,
-    pub rows: Vec<Vec<String>>,
+    pub header: Vec<TableCell>,
+    pub rows: Vec<Vec<TableCell>>,
+    pub pos: Option<SourcePos>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCell {
+    pub raw: String,
+    pub children: Vec<Inline>,
+}
+
+/// Inline content of paragraphs, headings and table cells.
+///
+/// Only populated by [`parse_to_ast`](crate::parse_to_ast); the `children`
+/// of blocks produced for HTML rendering are left empty.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Inline {
+    Text {
+        literal: String,
+    },
+    Emphasis {
+        children: Vec<Inline>,
+    },
+    Strong {
+        children: Vec<Inline>,
+    },
+    Strikethrough {
+        children: Vec<Inline>,
+    },
+    Highlight {
+        children: Vec<Inline>,
+    },
+    Underline {
+        children: Vec<Inline>,
+    },
+    /// `^text^` from [`enable_superscript`](crate::ParseOptions::enable_superscript).
+    Superscript {
+        children: Vec<Inline>,
+    },
+    /// `~text~` from [`enable_subscript`](crate::ParseOptions::enable_subscript).
+    Subscript {
+        children: Vec<Inline>,
+    },
+    Code {
+        literal: String,
+    },
+    Link {
+        dest: String,
+        title: Option<String>,
+        children: Vec<Inline>,
+    },
+    Image {
+        dest: String,
+        title: Option<String>,
+        children: Vec<Inline>,
+        attrs: Attributes,
+    },
+    Autolink {
+        url: String,
+        is_email: bool,
+    },
+    RawHtml {
+        literal: String,
+    },
+    /// `$…$`, `` $`…`$ `` or (with `display`) `$$…$$` math; `literal` is the
+    /// raw TeX source without the delimiters.
+    Math {
+        literal: String,
+        display: bool,
+    },
+    /// A `[^label]` reference to a footnote defined in the document. Numbers
+    /// are assigned when rendering, in order of first reference.
+    FootnoteReference {
+        label: String,
+    },
+    /// A `:name:` shortcode from [`enable_emoji`](crate::ParseOptions::enable_emoji);
+    /// `literal` is the Unicode emoji.
+    Emoji {
+        name: String,
+        literal: String,
+    },
+    /// A `:name:` shortcode resolved by
+    /// [`custom_emoji`](crate::ParseOptions::custom_emoji) to an image `url`.
+    CustomEmoji {
+        name: String,
+        url: String,
+    },
+    HardBreak,
+    SoftBreak,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -50,6 +263,56 @@ pub enum ListKind {
     Ordered(u8), // delimiter: b'.' or b')'
 }
 
+/// The kind of a [`Block::Alert`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlertKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl AlertKind {
+    /// Parses the word inside `[!…]`, case-insensitively.
+    pub fn from_marker(word: &str) -> Option<Self> {
+        const KINDS: [(&str, AlertKind); 5] = [
+            ("note", AlertKind::Note),
+            ("tip", AlertKind::Tip),
+            ("important", AlertKind::Important),
+            ("warning", AlertKind::Warning),
+            ("caution", AlertKind::Caution),
+        ];
+        KINDS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(word))
+            .map(|&(_, kind)| kind)
+    }
+
+    /// Lowercase name, as used in the `markdown-alert-{name}` class.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertKind::Note => "note",
+            AlertKind::Tip => "tip",
+            AlertKind::Important => "important",
+            AlertKind::Warning => "warning",
+            AlertKind::Caution => "caution",
+        }
+    }
+
+    /// Title shown at the top of the rendered alert.
+    pub fn title(self) -> &'static str {
+        match self {
+            AlertKind::Note => "Note",
+            AlertKind::Tip => "Tip",
+            AlertKind::Important => "Important",
+            AlertKind::Warning => "Warning",
+            AlertKind::Caution => "Caution",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TableAlignment {
@@ -58,3 +321,70 @@ pub enum TableAlignment {
     Center,
     Right,
 }
+
+/// A link reference definition (`[label]: dest "title"`), with entities and
+/// backslash escapes already resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkDefinition<'a> {
+    pub dest: &'a str,
+    pub title: Option<&'a str>,
+}
+
+/// Link reference definitions collected while parsing a document, keyed by
+/// normalized label (case-folded, whitespace collapsed).
+///
+/// Returned by [`parse_to_ast_with_refs`](crate::parse_to_ast_with_refs) and
+/// consumed by [`render_html_with_refs`](crate::render_html_with_refs) to resolve
+/// reference links in blocks whose inline `children` are empty.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkReferences {
+    pub(crate) map: LinkRefMap,
+}
+
+impl LinkReferences {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up a definition by label; the label is normalized first.
+    pub fn get(&self, label: &str) -> Option<LinkDefinition<'_>> {
+        self.map
+            .get(&*normalize_reference_label(label))
+            .map(|r| LinkDefinition {
+                dest: &r.href,
+                title: r.title.as_deref(),
+            })
+    }
+
+    /// Adds or replaces the definition for `label`.
+    pub fn insert(&mut self, label: &str, dest: &str, title: Option<&str>) {
+        self.map.insert(
+            normalize_reference_label(label).into_owned(),
+            LinkReference {
+                href: dest.into(),
+                title: title.map(Into::into),
+            },
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over `(normalized label, definition)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, LinkDefinition<'_>)> {
+        self.map.iter().map(|(label, r)| {
+            (
+                label.as_str(),
+                LinkDefinition {
+                    dest: &r.href,
+                    title: r.title.as_deref(),
+                },
+            )
+        })
+    }
+}
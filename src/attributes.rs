@@ -0,0 +1,219 @@
+use crate::ast::Attributes;
+use crate::html::escape_html_into;
+use crate::sanitize::is_allowed_url_attribute;
+use crate::{ParseOptions, RawHtmlMode};
+
+/// Parses a `{#id .class key=value key="quoted value"}` block at the start of
+/// `s`, returning the attributes and the number of bytes consumed. Classes
+/// are joined into one space-separated `class`; a repeated `#id` or key
+/// replaces the earlier one. Anything else inside the braces, or an empty
+/// block, means `s` does not start with attributes.
+pub(crate) fn parse_attributes(s: &str) -> Option<(Attributes, usize)> {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&b'{') {
+        return None;
+    }
+    let mut attrs = Attributes::new();
+    let mut i = 1;
+    loop {
+        while i < bytes.len() && matches!(bytes[i], b' ' | b'\t') {
+            i += 1;
+        }
+        match *bytes.get(i)? {
+            b'}' if !attrs.is_empty() => return Some((attrs, i + 1)),
+            b'#' | b'.' => {
+                let start = i + 1;
+                i = start;
+                while i < bytes.len() && !matches!(bytes[i], b' ' | b'\t' | b'\n' | b'}' | b'{') {
+                    i += 1;
+                }
+                if i == start {
+                    return None;
+                }
+                let value = &s[start..i];
+                if bytes[start - 1] == b'#' {
+                    attrs.insert("id".to_string(), value.to_string());
+                } else {
+                    attrs
+                        .entry("class".to_string())
+                        .and_modify(|c| {
+                            c.push(' ');
+                            c.push_str(value);
+                        })
+                        .or_insert_with(|| value.to_string());
+                }
+            }
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                let start = i;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'-' | b':'))
+                {
+                    i += 1;
+                }
+                let key = &s[start..i];
+                if bytes.get(i) != Some(&b'=') {
+                    return None;
+                }
+                i += 1;
+                let value = match *bytes.get(i)? {
+                    quote @ (b'"' | b'\'') => {
+                        let len = memchr::memchr(quote, &bytes[i + 1..])?;
+                        let value = &s[i + 1..i + 1 + len];
+                        i += len + 2;
+                        value
+                    }
+                    _ => {
+                        let start = i;
+                        while i < bytes.len()
+                            && !matches!(bytes[i], b' ' | b'\t' | b'\n' | b'}' | b'"' | b'\'')
+                        {
+                            i += 1;
+                        }
+                        &s[start..i]
+                    }
+                };
+                attrs.insert(key.to_string(), value.to_string());
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Splits a trailing attribute block off `text`, as in `Install {#install}`.
+/// The `{` must start `text` or follow whitespace, and only whitespace may
+/// follow the `}`. Returns the text before the block with trailing whitespace
+/// removed.
+pub(crate) fn split_trailing_attributes(text: &str) -> Option<(&str, Attributes)> {
+    let trimmed = text.trim_end();
+    if !trimmed.ends_with('}') {
+        return None;
+    }
+    let bytes = trimmed.as_bytes();
+    let mut end = trimmed.len();
+    while let Some(open) = memchr::memrchr(b'{', &bytes[..end]) {
+        end = open;
+        if open > 0 && !matches!(bytes[open - 1], b' ' | b'\t') {
+            continue;
+        }
+        if let Some((attrs, len)) = parse_attributes(&trimmed[open..])
+            && open + len == trimmed.len()
+        {
+            return Some((trimmed[..open].trim_end(), attrs));
+        }
+    }
+    None
+}
+
+/// Writes `attrs` as HTML attributes, `id` and `class` first, skipping the
+/// names in `skip`. With `safe_urls` or unless raw HTML is passed through,
+/// event handlers (`on…`) and `style` are dropped; with `safe_urls` or the
+/// sanitizer, URL attributes with a disallowed scheme are dropped too. `is_image` selects
+/// the image URL rules.
+pub(crate) fn push_attributes(
+    out: &mut String,
+    attrs: &Attributes,
+    skip: &[&str],
+    is_image: bool,
+    opts: &ParseOptions,
+) {
+    let trusted = opts.raw_html == RawHtmlMode::PassThrough && !opts.safe_urls;
+    let filter_urls = opts.safe_urls || opts.raw_html == RawHtmlMode::Sanitize;
+    let first = ["id", "class"]
+        .into_iter()
+        .filter_map(|key| attrs.get_key_value(key));
+    let rest = attrs
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "id" | "class"));
+    for (key, value) in first.chain(rest) {
+        if skip.contains(&key.as_str())
+            || (!trusted
+                && ((key.len() > 2 && key[..2].eq_ignore_ascii_case("on"))
+                    || key.eq_ignore_ascii_case("style")))
+            || (filter_urls
+                && !is_allowed_url_attribute(key, value, is_image, &opts.allowed_url_schemes))
+        {
+            continue;
+        }
+        out.push(' ');
+        out.push_str(key);
+        out.push_str("=\"");
+        escape_html_into(out, value);
+        out.push('"');
+    }
+}
+
+/// Writes `attrs` back as a `{#id .class key="value"}` block.
+pub(crate) fn write_attributes(out: &mut String, attrs: &Attributes) {
+    out.push('{');
+    let mut sep = "";
+    if let Some(id) = attrs.get("id") {
+        out.push('#');
+        out.push_str(id);
+        sep = " ";
+    }
+    for class in attrs
+        .get("class")
+        .into_iter()
+        .flat_map(|c| c.split_whitespace())
+    {
+        out.push_str(sep);
+        out.push('.');
+        out.push_str(class);
+        sep = " ";
+    }
+    for (key, value) in attrs
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "id" | "class"))
+    {
+        out.push_str(sep);
+        out.push_str(key);
+        out.push('=');
+        let quote = if value.contains('"') { '\'' } else { '"' };
+        out.push(quote);
+        out.push_str(value);
+        out.push(quote);
+        sep = " ";
+    }
+    out.push('}');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs(pairs: &[(&str, &str)]) -> Attributes {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_ids_classes_and_pairs() {
+        assert_eq!(
+            parse_attributes("{#a .b .c k=v title=\"x y\"} rest"),
+            Some((
+                attrs(&[("id", "a"), ("class", "b c"), ("k", "v"), ("title", "x y")]),
+                26
+            ))
+        );
+        assert_eq!(parse_attributes("{}"), None);
+        assert_eq!(parse_attributes("{# .b}"), None);
+        assert_eq!(parse_attributes("{k}"), None);
+        assert_eq!(parse_attributes("{.b"), None);
+    }
+
+    #[test]
+    fn splits_trailing_block() {
+        assert_eq!(
+            split_trailing_attributes("Install {#install .tab} "),
+            Some(("Install", attrs(&[("id", "install"), ("class", "tab")])))
+        );
+        assert_eq!(split_trailing_attributes("set{#x}"), None);
+        assert_eq!(split_trailing_attributes("a {#x} b"), None);
+        assert_eq!(
+            split_trailing_attributes("{.rust}"),
+            Some(("", attrs(&[("class", "rust")])))
+        );
+    }
+}
@@ -119,6 +119,43 @@ pub(super) fn parse_fence_start(line: &str) -> Option<(u8, usize, &str)> {
     Some((ch, count, info))
 }
 
+/// Parses a `$$` line opening display math, returning the trimmed text after
+/// the `$$`.
+pub(super) fn parse_math_fence_start(line: &str) -> Option<&str> {
+    line.strip_prefix("$$").map(str::trim)
+}
+
+/// Parses a `:::name attributes` line opening a container, returning the
+/// number of colons, the name and the trimmed rest of the line.
+pub(super) fn parse_container_start(line: &str) -> Option<(usize, &str, &str)> {
+    let fence_len = line.bytes().take_while(|&b| b == b':').count();
+    if fence_len < 3 {
+        return None;
+    }
+    let rest = line[fence_len..].trim();
+    let name_len = rest
+        .bytes()
+        .take_while(|&b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'))
+        .count();
+    if name_len == 0
+        || rest
+            .as_bytes()
+            .get(name_len)
+            .is_some_and(|b| !b.is_ascii_whitespace())
+    {
+        return None;
+    }
+    Some((fence_len, &rest[..name_len], rest[name_len..].trim_start()))
+}
+
+/// Whether `line` starts with the `: ` marker of a definition list entry.
+pub(super) fn is_definition_marker(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    bytes.first() == Some(&b':')
+        && matches!(bytes.get(1), Some(b' ' | b'\t'))
+        && !rest_is_blank(bytes, 2)
+}
+
 #[inline]
 pub(super) fn is_closing_fence(line: &[u8], fence_char: u8, fence_len: usize) -> bool {
     let len = line.len();
@@ -209,7 +246,7 @@ pub(super) fn parse_table_separator(line: &str) -> Option<Vec<TableAlignment>> {
     Some(alignments)
 }
 
-pub(super) fn parse_table_row(line: &str, num_cols: usize) -> Vec<String> {
+pub(super) fn parse_table_row(line: &str, num_cols: usize) -> Vec<TableCell> {
     let trimmed = trim_space_tab(line);
 
     let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
@@ -237,17 +274,17 @@ pub(super) fn parse_table_row(line: &str, num_cols: usize) -> Vec<String> {
             match memchr::memchr(b'|', &bytes[start..]) {
                 Some(offset) => {
                     let end = start + offset;
-                    cells.push(trim_space_tab(&inner[start..end]).to_string());
+                    cells.push(table_cell(&inner[start..end]));
                     start = end + 1;
                 }
                 None => {
-                    cells.push(trim_space_tab(&inner[start..]).to_string());
+                    cells.push(table_cell(&inner[start..]));
                     break;
                 }
             }
         }
         while cells.len() < num_cols {
-            cells.push(String::new());
+            cells.push(TableCell::default());
         }
         return cells;
     }
@@ -264,7 +301,7 @@ pub(super) fn parse_table_row(line: &str, num_cols: usize) -> Vec<String> {
             seg_start = i;
         } else if bytes[i] == b'|' {
             current.push_str(&inner[seg_start..i]);
-            cells.push(trim_space_tab(&current).to_string());
+            cells.push(table_cell(&current));
             current.clear();
             i += 1;
             seg_start = i;
@@ -273,15 +310,23 @@ pub(super) fn parse_table_row(line: &str, num_cols: usize) -> Vec<String> {
         }
     }
     current.push_str(&inner[seg_start..]);
-    cells.push(trim_space_tab(&current).to_string());
+    cells.push(table_cell(&current));
 
     while cells.len() < num_cols {
-        cells.push(String::new());
+        cells.push(TableCell::default());
     }
     cells.truncate(num_cols);
     cells
 }
 
+#[inline(always)]
+fn table_cell(s: &str) -> TableCell {
+    TableCell {
+        raw: trim_space_tab(s).to_string(),
+        children: Vec::new(),
+    }
+}
+
 #[inline(always)]
 fn trim_space_tab(s: &str) -> &str {
     let bytes = s.as_bytes();
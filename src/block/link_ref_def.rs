@@ -1,6 +1,19 @@
 use super::*;
+use crate::inline::is_footnote_label;
 use crate::{is_ascii_punctuation, utf8_char_len};
 
+/// Parses the `[^label]:` that opens a footnote definition, returning the
+/// label and the number of bytes up to and including the colon.
+pub(super) fn parse_footnote_def_start(input: &str) -> Option<(&str, usize)> {
+    let rest = input.strip_prefix("[^")?;
+    let close = memchr::memchr(b']', rest.as_bytes())?;
+    let label = &rest[..close];
+    if !is_footnote_label(label) || rest.as_bytes().get(close + 1) != Some(&b':') {
+        return None;
+    }
+    Some((label, close + 4))
+}
+
 pub(super) fn parse_link_ref_def(input: &str) -> Option<(String, String, Option<String>, usize)> {
     let bytes = input.as_bytes();
     if bytes.is_empty() || bytes[0] != b'[' {
@@ -105,7 +118,7 @@ pub(super) fn resolve_entities_and_escapes(s: &str) -> std::borrow::Cow<'_, str>
     std::borrow::Cow::Owned(out)
 }
 
-pub(super) fn resolve_entity_in_bytes(
+pub(crate) fn resolve_entity_in_bytes(
     bytes: &[u8],
     start: usize,
     out: &mut String,
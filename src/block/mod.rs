@@ -7,12 +7,20 @@ use html_block::*;
 use leaf_blocks::*;
 use link_ref_def::*;
 
-use crate::ParseOptions;
-use crate::ast::{Block, ListKind, TableAlignment};
+pub(crate) use link_ref_def::resolve_entity_in_bytes;
+
+use crate::ast::{
+    AlertKind, Attributes, Block, LinkReferences, ListKind, Position, SourcePos, TableAlignment,
+    TableCell,
+};
+use crate::attributes::split_trailing_attributes;
 use crate::entities;
-use crate::html::trim_cr;
-use crate::inline::{InlineBuffers, LinkRefMap};
+use crate::front_matter::{FrontMatter, ParseOutput, split_front_matter};
+use crate::handler::{DefaultHandler, HtmlHandler};
+use crate::html::{Slugger, trim_cr};
+use crate::inline::{InlineBuffers, LinkRefMap, inline_plain_text, parse_inlines};
 use crate::render::render_block;
+use crate::{ParseError, ParseOptions};
 use std::borrow::Cow;
 
 /// Parse a Markdown string and return the rendered HTML.
@@ -26,19 +34,111 @@ use std::borrow::Cow;
 /// assert!(html.contains("<strong>bold</strong>"));
 /// ```
 pub fn parse(markdown: &str, options: &ParseOptions) -> String {
-    let mut parser = BlockParser::new(markdown, options.enable_tables, options.enable_task_lists);
-    let doc = parser.parse();
+    parse_with_handler(markdown, options, &mut DefaultHandler)
+}
+
+/// Like [`parse`], letting `handler` override the HTML of individual nodes
+/// such as code blocks, headings, links and images (see [`HtmlHandler`]).
+pub fn parse_with_handler(
+    markdown: &str,
+    options: &ParseOptions,
+    handler: &mut dyn HtmlHandler,
+) -> String {
+    parse_checked(markdown, options, handler, options.front_matter).0
+}
+
+/// Like [`parse`], but always recognizes front matter (see
+/// [`ParseOptions::front_matter`]) and returns it next to the HTML.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse_with_front_matter, FrontMatterKind, ParseOptions};
+///
+/// let out = parse_with_front_matter("---\ntitle: Hi\n---\n# Hi", &ParseOptions::default());
+/// let front_matter = out.front_matter.unwrap();
+/// assert_eq!(front_matter.kind, FrontMatterKind::Yaml);
+/// assert_eq!(front_matter.raw, "title: Hi\n");
+/// assert_eq!(out.body, "<h1>Hi</h1>\n");
+/// ```
+pub fn parse_with_front_matter(markdown: &str, options: &ParseOptions) -> ParseOutput<String> {
+    let (body, _, front_matter) = parse_checked(markdown, options, &mut DefaultHandler, true);
+    ParseOutput { front_matter, body }
+}
+
+/// Like [`parse`], but returns an error instead of degrading the output when
+/// one of the resource limits in [`ParseOptions`] (`max_input_size`,
+/// `max_nesting_depth`, `max_inline_delimiters`, `max_output_size`) is hit.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{try_parse, ParseError, ParseOptions};
+///
+/// let opts = ParseOptions { max_nesting_depth: Some(2), ..Default::default() };
+/// assert!(try_parse("> > ok", &opts).is_ok());
+/// assert_eq!(try_parse("> > > deep", &opts), Err(ParseError::NestingTooDeep { limit: 2 }));
+/// ```
+pub fn try_parse(markdown: &str, options: &ParseOptions) -> Result<String, ParseError> {
+    if let Some(limit) = options.max_input_size
+        && markdown.len() > limit
+    {
+        return Err(ParseError::InputTooLarge {
+            size: markdown.len(),
+            limit,
+        });
+    }
+    match parse_checked(markdown, options, &mut DefaultHandler, options.front_matter) {
+        (html, None, _) => Ok(html),
+        (_, Some(err), _) => Err(err),
+    }
+}
+
+/// Parses and renders `markdown`, reporting the first resource limit that
+/// was hit while degrading gracefully.
+fn parse_checked(
+    markdown: &str,
+    options: &ParseOptions,
+    handler: &mut dyn HtmlHandler,
+    front_matter: bool,
+) -> (String, Option<ParseError>, Option<FrontMatter>) {
+    let mut parser = BlockParser::new(markdown, options);
+    if front_matter {
+        parser.skip_front_matter();
+    }
+    let mut doc = parser.parse();
+    let mut error = parser.limit_error.take();
     let refs = parser.ref_defs;
-    let mut out = String::with_capacity(markdown.len() + markdown.len() / 2);
+    if options.wants_heading_ids() || options.enable_attributes {
+        assign_heading_ids(&mut doc, &refs, options);
+    }
+    let capacity = markdown.len() + markdown.len() / 2;
+    let mut out = String::with_capacity(
+        options
+            .max_output_size
+            .map_or(capacity, |m| m.min(capacity)),
+    );
     let mut bufs = InlineBuffers::new();
-    render_block(&doc, &refs, &mut out, options, &mut bufs);
-    out
+    let complete = render_block(&doc, &refs, &mut out, options, &mut bufs, handler);
+    if let Some(limit) = options.max_inline_delimiters
+        && bufs.delimiter_limit_hit
+    {
+        error.get_or_insert(ParseError::TooManyDelimiters { limit });
+    }
+    if let Some(limit) = options.max_output_size
+        && !complete
+    {
+        error.get_or_insert(ParseError::OutputTooLarge { limit });
+    }
+    (out, error, parser.front_matter)
 }
 
 /// Parse a Markdown string and return the block-level AST.
 ///
-/// This returns the raw AST without rendering to HTML, useful for
+/// This returns the AST without rendering to HTML, useful for
 /// programmatic inspection or transformation of the document structure.
+/// Paragraphs, headings and table cells carry their parsed [`Inline`](crate::Inline)
+/// content in `children`, with reference links already resolved.
 ///
 /// # Examples
 ///
@@ -47,15 +147,164 @@ pub fn parse(markdown: &str, options: &ParseOptions) -> String {
 ///
 /// let ast = parse_to_ast("# Hello", &ParseOptions::default());
 /// match &ast {
-///     Block::Document { children } => {
+///     Block::Document { children, .. } => {
 ///         assert_eq!(children.len(), 1);
 ///     }
 ///     _ => panic!("expected Document"),
 /// }
 /// ```
 pub fn parse_to_ast(markdown: &str, options: &ParseOptions) -> Block {
-    let mut parser = BlockParser::new(markdown, options.enable_tables, options.enable_task_lists);
-    parser.parse()
+    parse_to_ast_with_refs(markdown, options).0
+}
+
+/// Like [`parse_to_ast`], but also returns the link reference definitions found
+/// in the document, so a modified tree can be rendered with
+/// [`render_html_with_refs`](crate::render_html_with_refs).
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse_to_ast_with_refs, ParseOptions};
+///
+/// let (_ast, refs) = parse_to_ast_with_refs("[x]\n\n[x]: /url", &ParseOptions::default());
+/// assert_eq!(refs.get("X").unwrap().dest, "/url");
+/// ```
+pub fn parse_to_ast_with_refs(markdown: &str, options: &ParseOptions) -> (Block, LinkReferences) {
+    let (doc, refs, _) = build_ast(markdown, options, options.front_matter);
+    (doc, refs)
+}
+
+/// Like [`parse_to_ast`], but always recognizes front matter (see
+/// [`ParseOptions::front_matter`]) and returns it next to the AST.
+pub fn parse_to_ast_with_front_matter(
+    markdown: &str,
+    options: &ParseOptions,
+) -> ParseOutput<Block> {
+    let (body, _, front_matter) = build_ast(markdown, options, true);
+    ParseOutput { front_matter, body }
+}
+
+fn build_ast(
+    markdown: &str,
+    options: &ParseOptions,
+    front_matter: bool,
+) -> (Block, LinkReferences, Option<FrontMatter>) {
+    let mut parser = BlockParser::new(markdown, options);
+    if front_matter {
+        parser.skip_front_matter();
+    }
+    let mut doc = parser.parse();
+    resolve_inlines(&mut doc, &parser.ref_defs, options);
+    if options.wants_heading_ids() || options.enable_attributes {
+        assign_heading_ids(&mut doc, &parser.ref_defs, options);
+    }
+    (
+        doc,
+        LinkReferences {
+            map: parser.ref_defs,
+        },
+        parser.front_matter,
+    )
+}
+
+fn resolve_inlines(root: &mut Block, refs: &LinkRefMap, opts: &ParseOptions) {
+    let mut bufs = InlineBuffers::new();
+    if opts.enable_footnotes {
+        bufs.footnotes.collect(root);
+    }
+    let mut stack: Vec<&mut Block> = Vec::with_capacity(32);
+    stack.push(root);
+
+    while let Some(block) = stack.pop() {
+        match block {
+            Block::Document { children, .. }
+            | Block::BlockQuote { children, .. }
+            | Block::Alert { children, .. }
+            | Block::List { children, .. }
+            | Block::ListItem { children, .. }
+            | Block::FootnoteDefinition { children, .. }
+            | Block::Container { children, .. }
+            | Block::DefinitionList { children, .. }
+            | Block::DefinitionDetails { children, .. } => stack.extend(children.iter_mut()),
+            Block::Paragraph { raw, children, .. }
+            | Block::Heading { raw, children, .. }
+            | Block::DefinitionTerm { raw, children, .. } => {
+                *children = parse_inlines(raw, refs, opts, &mut bufs);
+            }
+            Block::Table(td) => {
+                for cell in td.header.iter_mut().chain(td.rows.iter_mut().flatten()) {
+                    cell.children = parse_inlines(&cell.raw, refs, opts, &mut bufs);
+                }
+            }
+            Block::CodeBlock { .. }
+            | Block::HtmlBlock { .. }
+            | Block::MathBlock { .. }
+            | Block::ThematicBreak { .. } => {}
+        }
+    }
+}
+
+/// Sets `id` on every heading without an explicit one, in document order so
+/// that repeated slugs are numbered top to bottom.
+/// Gives every heading a slug `id` when `opts` asks for heading ids, and
+/// makes explicit `{#id}`s unique. Slugs never take an explicit id, even one
+/// further down the document.
+fn assign_heading_ids(root: &mut Block, refs: &LinkRefMap, opts: &ParseOptions) {
+    let mut slugger = Slugger::default();
+    let mut bufs = InlineBuffers::new();
+    if opts.enable_footnotes {
+        bufs.footnotes.collect(root);
+    }
+    let mut headings: Vec<&mut Block> = Vec::new();
+    let mut stack: Vec<&mut Block> = Vec::with_capacity(32);
+    stack.push(root);
+
+    while let Some(block) = stack.pop() {
+        if let Block::Heading { attrs, .. } = block {
+            if let Some(id) = attrs.get("id") {
+                slugger.reserve(id);
+            }
+            headings.push(block);
+            continue;
+        }
+        match block {
+            Block::Document { children, .. }
+            | Block::BlockQuote { children, .. }
+            | Block::Alert { children, .. }
+            | Block::List { children, .. }
+            | Block::ListItem { children, .. }
+            | Block::FootnoteDefinition { children, .. }
+            | Block::Container { children, .. }
+            | Block::DefinitionList { children, .. }
+            | Block::DefinitionDetails { children, .. } => stack.extend(children.iter_mut().rev()),
+            _ => {}
+        }
+    }
+
+    for heading in headings {
+        let Block::Heading {
+            raw,
+            children,
+            id,
+            attrs,
+            ..
+        } = heading
+        else {
+            continue;
+        };
+        // An explicit `{#id}` wins over the slug.
+        if let Some(explicit) = attrs.get_mut("id") {
+            *explicit = slugger.claim(explicit);
+        } else if opts.wants_heading_ids() {
+            let text = if children.is_empty() {
+                inline_plain_text(&parse_inlines(raw, refs, opts, &mut bufs))
+            } else {
+                inline_plain_text(children)
+            };
+            let slug = slugger.slug(&text);
+            *id = (!slug.is_empty()).then_some(slug);
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -252,8 +501,8 @@ struct FencedCodeData {
 #[derive(Clone, Debug)]
 struct TableData {
     alignments: Vec<TableAlignment>,
-    header: Vec<String>,
-    rows: Vec<Vec<String>>,
+    header: Vec<TableCell>,
+    rows: Vec<Vec<TableCell>>,
 }
 
 #[derive(Clone, Debug)]
@@ -271,8 +520,25 @@ enum OpenBlockType {
     },
     Paragraph,
     Table(Box<TableData>),
+    FootnoteDefinition {
+        label: String,
+    },
+    Container(Box<ContainerData>),
+    DefinitionDetails {
+        content_col: usize,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct ContainerData {
+    fence_len: usize,
+    name: String,
+    attributes: String,
 }
 
+/// Indentation of the continuation lines of a footnote definition.
+const FOOTNOTE_INDENT: usize = 4;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum HtmlBlockEnd {
     EndTag(&'static str),
@@ -294,6 +560,7 @@ struct OpenBlock {
     checked: Option<bool>,
     list_start: u32,
     list_kind: Option<ListKind>,
+    pos: SourcePos,
 }
 
 impl OpenBlock {
@@ -309,9 +576,16 @@ impl OpenBlock {
             checked: None,
             list_start: 0,
             list_kind: None,
+            pos: SourcePos::default(),
         }
     }
 
+    #[inline]
+    fn at(mut self, pos: SourcePos) -> Self {
+        self.pos = pos;
+        self
+    }
+
     #[inline]
     fn with_content_capacity(block_type: OpenBlockType, cap: usize) -> Self {
         Self {
@@ -327,23 +601,148 @@ pub(crate) struct BlockParser<'a> {
     open: Vec<OpenBlock>,
     enable_tables: bool,
     enable_task_lists: bool,
+    enable_footnotes: bool,
+    enable_math: bool,
+    enable_alerts: bool,
+    enable_containers: bool,
+    enable_definition_lists: bool,
+    enable_attributes: bool,
+    source_positions: bool,
+    max_nesting_depth: Option<usize>,
+    /// Set when a block marker was kept as text because of `max_nesting_depth`.
+    pub(crate) limit_error: Option<ParseError>,
+    pub(crate) front_matter: Option<FrontMatter>,
+    /// Byte offset in `input` where parsing starts, past any front matter.
+    body_start: usize,
     open_blockquotes: usize,
+    /// Open `:::` containers; like block quotes, they disable the fast path
+    /// in `process_line` that only checks list indentation.
+    open_containers: usize,
+    /// Indentation required by the open list items and footnote definitions.
     list_indent_sum: usize,
+    /// 1-based number of the line being processed.
+    line_no: usize,
+    /// Byte offset of the current line within `input`.
+    line_start: usize,
+    /// Length of the current line without trailing whitespace. Only tracked
+    /// when `source_positions` is enabled.
+    line_content_len: usize,
 }
 
 impl<'a> BlockParser<'a> {
-    pub fn new(input: &'a str, enable_tables: bool, enable_task_lists: bool) -> Self {
-        let doc = OpenBlock::new(OpenBlockType::Document);
+    pub fn new(mut input: &'a str, options: &ParseOptions) -> Self {
+        if let Some(limit) = options.max_input_size
+            && input.len() > limit
+        {
+            let mut end = limit;
+            while !input.is_char_boundary(end) {
+                end -= 1;
+            }
+            input = &input[..end];
+        }
+        let doc = OpenBlock::new(OpenBlockType::Document).at(SourcePos {
+            start: Position {
+                line: 1,
+                column: 1,
+                offset: 0,
+            },
+            end: Position {
+                line: 1,
+                column: 1,
+                offset: 0,
+            },
+        });
         let mut open = Vec::with_capacity(16);
         open.push(doc);
         Self {
             input,
             ref_defs: LinkRefMap::default(),
             open,
-            enable_tables,
-            enable_task_lists,
+            enable_tables: options.enable_tables,
+            enable_task_lists: options.enable_task_lists,
+            enable_footnotes: options.enable_footnotes,
+            enable_math: options.enable_math,
+            enable_alerts: options.enable_alerts,
+            enable_containers: options.enable_containers,
+            enable_definition_lists: options.enable_definition_lists,
+            enable_attributes: options.enable_attributes,
+            source_positions: options.source_positions || options.sourcepos,
+            max_nesting_depth: options.max_nesting_depth,
+            limit_error: None,
+            front_matter: None,
+            body_start: 0,
             open_blockquotes: 0,
+            open_containers: 0,
             list_indent_sum: 0,
+            line_no: 0,
+            line_start: 0,
+            line_content_len: 0,
+        }
+    }
+
+    /// Whether opening another block quote, list item, footnote definition,
+    /// container or definition would exceed
+    /// `max_nesting_depth`; records the error if so.
+    fn nesting_full(&mut self) -> bool {
+        let Some(limit) = self.max_nesting_depth else {
+            return false;
+        };
+        let depth = self
+            .open
+            .iter()
+            .filter(|b| {
+                matches!(
+                    b.block_type,
+                    OpenBlockType::BlockQuote
+                        | OpenBlockType::ListItem { .. }
+                        | OpenBlockType::FootnoteDefinition { .. }
+                        | OpenBlockType::Container(..)
+                        | OpenBlockType::DefinitionDetails { .. }
+                )
+            })
+            .count();
+        if depth < limit {
+            return false;
+        }
+        self.limit_error
+            .get_or_insert(ParseError::NestingTooDeep { limit });
+        true
+    }
+
+    /// Position of byte `col` of the current line.
+    #[inline(always)]
+    fn point(&self, col: usize) -> Position {
+        Position {
+            line: self.line_no,
+            column: col + 1,
+            offset: self.line_start + col,
+        }
+    }
+
+    /// Range from byte `col` of the current line to its last non-whitespace byte.
+    #[inline(always)]
+    fn line_pos(&self, col: usize) -> SourcePos {
+        SourcePos {
+            start: self.point(col),
+            end: self.point(self.line_content_len.max(col)),
+        }
+    }
+
+    #[inline(always)]
+    fn block_pos(&self, pos: SourcePos) -> Option<SourcePos> {
+        if self.source_positions {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    /// Moves front matter at the start of the input out of the document.
+    pub(crate) fn skip_front_matter(&mut self) {
+        if let Some((front_matter, body_start, lines)) = split_front_matter(self.input) {
+            self.front_matter = Some(front_matter);
+            self.body_start = body_start;
+            self.line_no = lines;
         }
     }
 
@@ -351,13 +750,21 @@ impl<'a> BlockParser<'a> {
         let input = self.input;
         let bytes = input.as_bytes();
         let len = bytes.len();
-        let mut start = 0;
+        let mut start = self.body_start;
         while start < len {
             let end = memchr_newline(bytes, start);
             let raw_line = &input[start..end];
             let raw_line = trim_cr(raw_line);
+            self.line_no += 1;
+            self.line_start = start;
             let line = Line::new(raw_line);
-            self.process_line(line);
+            if self.source_positions {
+                self.line_content_len = raw_line.trim_end().len();
+                self.process_line(line);
+                self.extend_open_blocks();
+            } else {
+                self.process_line(line);
+            }
 
             if self.open.len() == 2
                 && let OpenBlockType::FencedCode(ref fc_data) = self.open[1].block_type
@@ -378,6 +785,17 @@ impl<'a> BlockParser<'a> {
         let doc = self.open.pop().unwrap();
         Block::Document {
             children: doc.children,
+            pos: self.block_pos(doc.pos),
+        }
+    }
+
+    /// Extends every open block to the end of the current line, unless it is blank.
+    fn extend_open_blocks(&mut self) {
+        if self.line_content_len > 0 {
+            let end = self.point(self.line_content_len);
+            for block in self.open.iter_mut() {
+                block.pos.end = end;
+            }
         }
     }
 
@@ -404,6 +822,13 @@ impl<'a> BlockParser<'a> {
                 line_end
             };
 
+            if self.source_positions {
+                self.line_no += 1;
+                self.line_start = pos;
+                self.line_content_len = input[pos..check_end].trim_end().len();
+                self.extend_open_blocks();
+            }
+
             if is_closing_fence(&bytes[pos..check_end], fence_char, fence_len) {
                 if pos > content_start {
                     self.push_bulk_content(input, content_start, pos, has_cr);
@@ -445,11 +870,13 @@ impl<'a> BlockParser<'a> {
         let len = self.open.len();
         for i in (1..len).rev() {
             match &self.open[i].block_type {
-                OpenBlockType::ListItem { .. } => {
+                OpenBlockType::ListItem { .. } | OpenBlockType::DefinitionDetails { .. } => {
                     self.open[i].had_blank_in_item = true;
                     break;
                 }
-                OpenBlockType::BlockQuote => {
+                OpenBlockType::BlockQuote
+                | OpenBlockType::FootnoteDefinition { .. }
+                | OpenBlockType::Container(..) => {
                     break;
                 }
                 _ => {}
@@ -464,9 +891,16 @@ impl<'a> BlockParser<'a> {
             OpenBlockType::BlockQuote => {
                 self.open_blockquotes -= 1;
             }
-            OpenBlockType::ListItem { content_col, .. } => {
+            OpenBlockType::ListItem { content_col, .. }
+            | OpenBlockType::DefinitionDetails { content_col } => {
                 self.list_indent_sum -= content_col;
             }
+            OpenBlockType::FootnoteDefinition { .. } => {
+                self.list_indent_sum -= FOOTNOTE_INDENT;
+            }
+            OpenBlockType::Container(..) => {
+                self.open_containers -= 1;
+            }
             _ => {}
         }
         let finalized = self.finalize_block(block);
@@ -24,6 +24,11 @@ fn advance_past_blockquote_marker(line: &mut Line) {
     }
 }
 
+/// Number of line breaks in paragraph content, ignoring trailing ones.
+fn line_count(content: &str) -> usize {
+    memchr::memchr_iter(b'\n', content.trim_end().as_bytes()).count()
+}
+
 impl<'a> BlockParser<'a> {
     #[inline(never)]
     pub(super) fn process_line(&mut self, mut line: Line<'a>) {
@@ -34,7 +39,11 @@ impl<'a> BlockParser<'a> {
         let mut all_matched = true;
         let mut i = 1;
 
-        if num_open > 2 && line.partial_spaces == 0 && self.open_blockquotes == 0 {
+        if num_open > 2
+            && line.partial_spaces == 0
+            && self.open_blockquotes == 0
+            && self.open_containers == 0
+        {
             let tip_is_leaf = matches!(
                 self.open[num_open - 1].block_type,
                 OpenBlockType::Paragraph
@@ -121,6 +130,44 @@ impl<'a> BlockParser<'a> {
                         break;
                     }
                 }
+                OpenBlockType::DefinitionDetails { content_col } => {
+                    let content_col = *content_col;
+                    let (ns_col, ns_off, ns_byte) = line.peek_nonspace_col();
+                    let is_blank = ns_byte == 0 && ns_off >= line.raw.len();
+                    if is_blank {
+                        let _ = line.skip_indent(content_col);
+                        matched = i + 1;
+                    } else if ns_col - line.col_offset >= content_col {
+                        line.skip_indent(content_col);
+                        matched = i + 1;
+                    } else {
+                        all_matched = false;
+                        break;
+                    }
+                }
+                OpenBlockType::FootnoteDefinition { .. } => {
+                    let (ns_col, ns_off, ns_byte) = line.peek_nonspace_col();
+                    let is_blank = ns_byte == 0 && ns_off >= line.raw.len();
+                    if is_blank {
+                        matched = i + 1;
+                    } else if ns_col - line.col_offset >= FOOTNOTE_INDENT {
+                        line.skip_indent(FOOTNOTE_INDENT);
+                        matched = i + 1;
+                    } else {
+                        all_matched = false;
+                        break;
+                    }
+                }
+                OpenBlockType::Container(data) => {
+                    if is_closing_fence(line.remainder().as_bytes(), b':', data.fence_len) {
+                        self.open[i].pos.end = self.point(self.line_content_len);
+                        while self.open.len() > i {
+                            self.close_top_block();
+                        }
+                        return;
+                    }
+                    matched = i + 1;
+                }
                 OpenBlockType::FencedCode(..)
                 | OpenBlockType::IndentedCode
                 | OpenBlockType::HtmlBlock { .. }
@@ -158,6 +205,7 @@ impl<'a> BlockParser<'a> {
                     let fl = fc_data.fence_len;
                     let fi = fc_data.fence_indent;
                     if is_closing_fence(line.remainder().as_bytes(), fc, fl) {
+                        self.open[tip_idx].pos.end = self.point(self.line_content_len);
                         self.close_top_block();
                         return;
                     }
@@ -209,6 +257,7 @@ impl<'a> BlockParser<'a> {
                     }
                     self.open[tip_idx].content.push_str(line.remainder());
                     if html_block_ends(&end_condition, line.remainder()) {
+                        self.open[tip_idx].pos.end = self.point(self.line_content_len);
                         self.close_top_block();
                     }
                     return;
@@ -256,17 +305,51 @@ impl<'a> BlockParser<'a> {
                         let num_cols = alignments.len();
                         let header = parse_table_row(&self.open[tip_idx].content, num_cols);
                         if header.len() == num_cols {
-                            self.open.pop();
-                            self.open.push(OpenBlock::new(OpenBlockType::Table(Box::new(
-                                TableData {
+                            let para = self.open.pop().unwrap();
+                            self.open.push(
+                                OpenBlock::new(OpenBlockType::Table(Box::new(TableData {
                                     alignments,
                                     header,
                                     rows: Vec::with_capacity(16),
-                                },
-                            ))));
+                                })))
+                                .at(para.pos),
+                            );
                             return;
                         }
                     }
+                    if indent <= 3
+                        && ns_byte == b'$'
+                        && self.enable_math
+                        && let Some(content) = parse_math_fence_start(rest)
+                    {
+                        self.close_top_block();
+                        self.start_math_block(content, indent, ns_off);
+                        return;
+                    }
+                    if indent <= 3
+                        && ns_byte == b':'
+                        && self.enable_definition_lists
+                        && is_definition_marker(rest)
+                    {
+                        let para = self.open.pop().unwrap();
+                        if let Some(Block::Paragraph { raw, pos, .. }) = self.finalize_block(para) {
+                            self.push_definition_term(raw, pos);
+                        }
+                        self.open_new_blocks(line);
+                        return;
+                    }
+                    if indent <= 3
+                        && ((ns_byte == b'['
+                            && self.enable_footnotes
+                            && parse_footnote_def_start(rest).is_some())
+                            || (ns_byte == b':'
+                                && self.enable_containers
+                                && parse_container_start(rest).is_some()))
+                    {
+                        self.close_top_block();
+                        self.open_new_blocks(line);
+                        return;
+                    }
                     if indent > 3
                         || !matches!(
                             ns_byte,
@@ -298,7 +381,8 @@ impl<'a> BlockParser<'a> {
                             if remaining.is_empty() {
                                 self.open.pop();
                                 let mut para =
-                                    OpenBlock::with_content_capacity(OpenBlockType::Paragraph, 128);
+                                    OpenBlock::with_content_capacity(OpenBlockType::Paragraph, 128)
+                                        .at(self.line_pos(ns_off));
                                 para.content.push_str(rest);
                                 self.open.push(para);
                                 return;
@@ -306,38 +390,46 @@ impl<'a> BlockParser<'a> {
                             let mut raw = remaining;
                             let trimmed_len = raw.trim_end().len();
                             raw.truncate(trimmed_len);
-                            self.open.pop();
-                            let heading = Block::Heading { level, raw };
+                            let para = self.open.pop().unwrap();
+                            let skipped = line_count(&content) - line_count(&raw);
+                            let pos = self.block_pos(SourcePos {
+                                start: self.skip_lines(para.pos.start, skipped),
+                                end: self.point(self.line_content_len),
+                            });
+                            let heading = self.heading(level, raw, pos);
                             let parent = self.open.last_mut().unwrap();
                             parent.children.push(heading);
                             return;
                         }
                         if is_thematic_break(rest) {
                             self.close_top_block();
+                            let pos = self.block_pos(self.line_pos(ns_off));
                             let parent = self.open.last_mut().unwrap();
-                            parent.children.push(Block::ThematicBreak);
+                            parent.children.push(Block::ThematicBreak { pos });
                             return;
                         }
                         if let Some((level, content)) = parse_atx_heading(rest) {
                             self.close_top_block();
+                            let pos = self.block_pos(self.line_pos(ns_off));
+                            let heading = self.heading(level, content.to_string(), pos);
                             let parent = self.open.last_mut().unwrap();
-                            parent.children.push(Block::Heading {
-                                level,
-                                raw: content.to_string(),
-                            });
+                            parent.children.push(heading);
                             return;
                         }
                         if let Some((fence_char, fence_len, info)) = parse_fence_start(rest) {
                             self.close_top_block();
-                            self.open.push(OpenBlock::with_content_capacity(
-                                OpenBlockType::FencedCode(Box::new(FencedCodeData {
-                                    fence_char,
-                                    fence_len,
-                                    fence_indent: indent,
-                                    info: resolve_entities_and_escapes(info).into_owned(),
-                                })),
-                                256,
-                            ));
+                            self.open.push(
+                                OpenBlock::with_content_capacity(
+                                    OpenBlockType::FencedCode(Box::new(FencedCodeData {
+                                        fence_char,
+                                        fence_len,
+                                        fence_indent: indent,
+                                        info: resolve_entities_and_escapes(info).into_owned(),
+                                    })),
+                                    256,
+                                )
+                                .at(self.line_pos(ns_off)),
+                            );
                             return;
                         }
                         if let Some(end_condition) = parse_html_block_start(rest, true) {
@@ -345,12 +437,15 @@ impl<'a> BlockParser<'a> {
                             let mut block = OpenBlock::with_content_capacity(
                                 OpenBlockType::HtmlBlock { end_condition },
                                 128,
-                            );
+                            )
+                            .at(self.line_pos(ns_off));
                             block.content.push_str(line.remainder());
                             if html_block_ends(&end_condition, line.remainder()) {
+                                let pos = self.block_pos(block.pos);
                                 let parent = self.open.last_mut().unwrap();
                                 parent.children.push(Block::HtmlBlock {
                                     literal: block.content,
+                                    pos,
                                 });
                             } else {
                                 self.open.push(block);
@@ -399,7 +494,18 @@ impl<'a> BlockParser<'a> {
                         || is_thematic_break(rest)
                         || parse_atx_heading(rest).is_some()
                         || parse_fence_start(rest).is_some()
-                        || parse_html_block_start(rest, false).is_some());
+                        || parse_html_block_start(rest, false).is_some()
+                        || (self.enable_footnotes && parse_footnote_def_start(rest).is_some())
+                        || (self.enable_math && parse_math_fence_start(rest).is_some())
+                        || (self.enable_containers && parse_container_start(rest).is_some())
+                        || (self.enable_definition_lists
+                            && is_definition_marker(rest)
+                            && (matched..num_open).any(|idx| {
+                                matches!(
+                                    self.open[idx].block_type,
+                                    OpenBlockType::DefinitionDetails { .. }
+                                )
+                            })));
 
                 if !can_start_new {
                     let marker = if indent <= 3 {
@@ -443,7 +549,10 @@ impl<'a> BlockParser<'a> {
                 let len = self.open.len();
                 let mut found_list_item = false;
                 for i in (1..len).rev() {
-                    if matches!(self.open[i].block_type, OpenBlockType::ListItem { .. }) {
+                    if matches!(
+                        self.open[i].block_type,
+                        OpenBlockType::ListItem { .. } | OpenBlockType::DefinitionDetails { .. }
+                    ) {
                         self.open[i].had_blank_in_item = true;
                         found_list_item = true;
                         break;
@@ -451,21 +560,20 @@ impl<'a> BlockParser<'a> {
                 }
                 if !found_list_item {
                     let parent = self.open.last_mut().unwrap();
-                    if parent
-                        .children
-                        .last()
-                        .is_some_and(|c| matches!(c, Block::List { .. }))
-                    {
+                    if parent.children.last().is_some_and(|c| {
+                        matches!(c, Block::List { .. } | Block::DefinitionList { .. })
+                    }) {
                         parent.list_has_blank_between = true;
                     }
                 }
                 return;
             }
 
-            if indent <= 3 && first_byte == b'>' {
+            if indent <= 3 && first_byte == b'>' && !self.nesting_full() {
                 line.advance_to_nonspace();
                 advance_past_blockquote_marker(&mut line);
-                self.open.push(OpenBlock::new(OpenBlockType::BlockQuote));
+                self.open
+                    .push(OpenBlock::new(OpenBlockType::BlockQuote).at(self.line_pos(ns_off)));
                 self.open_blockquotes += 1;
                 continue;
             }
@@ -479,11 +587,14 @@ impl<'a> BlockParser<'a> {
 
                 if matches!(first_byte, b'-' | b'*' | b'+' | b'0'..=b'9') {
                     if matches!(first_byte, b'-' | b'*') && is_thematic_break(rest) {
+                        let pos = self.block_pos(self.line_pos(ns_off));
                         let parent = self.open.last_mut().unwrap();
-                        parent.children.push(Block::ThematicBreak);
+                        parent.children.push(Block::ThematicBreak { pos });
                         return;
                     }
-                    if let Some(marker) = parse_list_marker(rest) {
+                    if let Some(marker) = parse_list_marker(rest)
+                        && !self.nesting_full()
+                    {
                         let marker_indent = indent;
                         line.advance_to_nonspace();
                         let rest_is_blank = self.start_list_item(&mut line, marker, marker_indent);
@@ -494,48 +605,100 @@ impl<'a> BlockParser<'a> {
                     }
                 }
                 if matches!(first_byte, b'_') && is_thematic_break(rest) {
+                    let pos = self.block_pos(self.line_pos(ns_off));
                     let parent = self.open.last_mut().unwrap();
-                    parent.children.push(Block::ThematicBreak);
+                    parent.children.push(Block::ThematicBreak { pos });
                     return;
                 }
                 if let Some((level, content)) = parse_atx_heading(rest) {
                     line.advance_to_nonspace();
+                    let pos = self.block_pos(self.line_pos(ns_off));
+                    let heading = self.heading(level, content.to_string(), pos);
                     let parent = self.open.last_mut().unwrap();
-                    parent.children.push(Block::Heading {
-                        level,
-                        raw: content.to_string(),
-                    });
+                    parent.children.push(heading);
                     return;
                 }
                 if let Some((fence_char, fence_len, info)) = parse_fence_start(rest) {
-                    self.open.push(OpenBlock::with_content_capacity(
-                        OpenBlockType::FencedCode(Box::new(FencedCodeData {
-                            fence_char,
-                            fence_len,
-                            fence_indent: indent,
-                            info: resolve_entities_and_escapes(info).into_owned(),
-                        })),
-                        256,
-                    ));
+                    self.open.push(
+                        OpenBlock::with_content_capacity(
+                            OpenBlockType::FencedCode(Box::new(FencedCodeData {
+                                fence_char,
+                                fence_len,
+                                fence_indent: indent,
+                                info: resolve_entities_and_escapes(info).into_owned(),
+                            })),
+                            256,
+                        )
+                        .at(self.line_pos(ns_off)),
+                    );
+                    return;
+                }
+                if first_byte == b':'
+                    && self.enable_definition_lists
+                    && is_definition_marker(rest)
+                    && matches!(
+                        self.open.last().unwrap().children.last(),
+                        Some(Block::DefinitionList { .. } | Block::Paragraph { .. })
+                    )
+                    && !self.nesting_full()
+                {
+                    let parent = self.open.last_mut().unwrap();
+                    if matches!(parent.children.last(), Some(Block::Paragraph { .. }))
+                        && let Some(Block::Paragraph { raw, pos, .. }) = parent.children.pop()
+                    {
+                        // `Term`, blank line, `: definition`.
+                        self.push_definition_term(raw, pos);
+                        self.open.last_mut().unwrap().list_has_blank_between = true;
+                    }
+                    self.start_definition_details(&mut line, indent, ns_off);
+                    continue;
+                }
+                if first_byte == b':'
+                    && self.enable_containers
+                    && let Some((fence_len, name, attributes)) = parse_container_start(rest)
+                    && !self.nesting_full()
+                {
+                    let data = ContainerData {
+                        fence_len,
+                        name: name.to_string(),
+                        attributes: attributes.to_string(),
+                    };
+                    self.open.push(
+                        OpenBlock::new(OpenBlockType::Container(Box::new(data)))
+                            .at(self.line_pos(ns_off)),
+                    );
+                    self.open_containers += 1;
+                    return;
+                }
+                if first_byte == b'$'
+                    && self.enable_math
+                    && let Some(content) = parse_math_fence_start(rest)
+                {
+                    self.start_math_block(content, indent, ns_off);
                     return;
                 }
                 if let Some(end_condition) = parse_html_block_start(rest, false) {
                     let mut block = OpenBlock::with_content_capacity(
                         OpenBlockType::HtmlBlock { end_condition },
                         128,
-                    );
+                    )
+                    .at(self.line_pos(ns_off));
                     block.content.push_str(line.remainder());
                     if html_block_ends(&end_condition, line.remainder()) {
+                        let pos = self.block_pos(block.pos);
                         let parent = self.open.last_mut().unwrap();
                         parent.children.push(Block::HtmlBlock {
                             literal: block.content,
+                            pos,
                         });
                     } else {
                         self.open.push(block);
                     }
                     return;
                 }
-                if let Some(marker) = parse_list_marker(rest) {
+                if let Some(marker) = parse_list_marker(rest)
+                    && !self.nesting_full()
+                {
                     let marker_indent = indent;
                     line.advance_to_nonspace();
                     let rest_is_blank = self.start_list_item(&mut line, marker, marker_indent);
@@ -544,13 +707,30 @@ impl<'a> BlockParser<'a> {
                     }
                     continue;
                 }
+                if first_byte == b'['
+                    && self.enable_footnotes
+                    && let Some((label, consumed)) = parse_footnote_def_start(rest)
+                    && !self.nesting_full()
+                {
+                    let label = label.to_string();
+                    line.advance_to_nonspace();
+                    line.byte_offset += consumed;
+                    line.col_offset += consumed;
+                    self.open.push(
+                        OpenBlock::new(OpenBlockType::FootnoteDefinition { label })
+                            .at(self.line_pos(ns_off)),
+                    );
+                    self.list_indent_sum += FOOTNOTE_INDENT;
+                    continue;
+                }
             } else {
                 let tip = self.open.last().unwrap();
                 if !matches!(tip.block_type, OpenBlockType::Paragraph) {
                     let _ = line.skip_indent(4);
                     let content = line.remainder_with_partial();
                     let mut block =
-                        OpenBlock::with_content_capacity(OpenBlockType::IndentedCode, 128);
+                        OpenBlock::with_content_capacity(OpenBlockType::IndentedCode, 128)
+                            .at(self.line_pos(ns_off));
                     block.content.push_str(&content);
                     self.open.push(block);
                     return;
@@ -558,7 +738,8 @@ impl<'a> BlockParser<'a> {
             }
 
             line.advance_to_nonspace();
-            let mut block = OpenBlock::with_content_capacity(OpenBlockType::Paragraph, 128);
+            let mut block = OpenBlock::with_content_capacity(OpenBlockType::Paragraph, 128)
+                .at(self.line_pos(ns_off));
             block.content.push_str(line.remainder());
             self.open.push(block);
             return;
@@ -572,6 +753,7 @@ impl<'a> BlockParser<'a> {
         marker: ListMarkerInfo,
         marker_indent: usize,
     ) -> bool {
+        let marker_off = line.byte_offset;
         line.advance_columns(marker.marker_len);
         let (ns_col, ns_off, ns_byte) = line.peek_nonspace_col();
         let rest_blank = ns_byte == 0 && ns_off >= line.raw.len();
@@ -617,7 +799,8 @@ impl<'a> BlockParser<'a> {
         let mut item = OpenBlock::new(OpenBlockType::ListItem {
             content_col,
             started_blank: rest_blank,
-        });
+        })
+        .at(self.line_pos(marker_off));
         item.list_kind = Some(list_kind);
         item.list_start = marker.start_num;
         item.checked = checked;
@@ -626,14 +809,238 @@ impl<'a> BlockParser<'a> {
         rest_blank
     }
 
+    /// Opens a `$$` display math block, or adds a complete one-line
+    /// `$$ … $$` block. `content` is the text after the opening `$$`.
+    fn start_math_block(&mut self, content: &str, indent: usize, ns_off: usize) {
+        let pos = self.line_pos(ns_off);
+        if let Some(literal) = content.strip_suffix("$$") {
+            let pos = self.block_pos(pos);
+            let parent = self.open.last_mut().unwrap();
+            parent.children.push(Block::MathBlock {
+                literal: literal.trim_end().to_string(),
+                pos,
+            });
+            return;
+        }
+        let mut block = OpenBlock::with_content_capacity(
+            OpenBlockType::FencedCode(Box::new(FencedCodeData {
+                fence_char: b'$',
+                fence_len: 2,
+                fence_indent: indent,
+                info: String::new(),
+            })),
+            128,
+        )
+        .at(pos);
+        if !content.is_empty() {
+            block.content.push_str(content);
+            block.content.push('\n');
+        }
+        self.open.push(block);
+    }
+
+    /// Adds a paragraph followed by a `: ` line as a term to the definition
+    /// list ending the current container, starting a new list if needed.
+    fn push_definition_term(&mut self, raw: String, pos: Option<SourcePos>) {
+        let parent = self.open.last_mut().unwrap();
+        let term = Block::DefinitionTerm {
+            raw,
+            children: Vec::new(),
+            pos,
+        };
+        if let Some(Block::DefinitionList {
+            children,
+            pos: list_pos,
+            ..
+        }) = parent.children.last_mut()
+        {
+            if let (Some(list_pos), Some(pos)) = (list_pos, pos) {
+                list_pos.end = pos.end;
+            }
+            children.push(term);
+        } else {
+            parent.children.push(Block::DefinitionList {
+                tight: true,
+                children: vec![term],
+                pos,
+            });
+        }
+        // Blank lines between groups of terms do not make the list loose.
+        parent.list_has_blank_between = false;
+    }
+
+    /// Opens a `<dd>` after its `: ` marker; continuation lines must be
+    /// indented to the column of the text after the marker.
+    fn start_definition_details(
+        &mut self,
+        line: &mut Line<'a>,
+        marker_indent: usize,
+        ns_off: usize,
+    ) {
+        line.advance_to_nonspace();
+        line.advance_columns(1);
+        let (ns_col, _, _) = line.peek_nonspace_col();
+        let spaces = match ns_col - line.col_offset {
+            n @ 1..=4 => n,
+            _ => 1,
+        };
+        let _ = line.skip_indent(spaces);
+        let content_col = marker_indent + 1 + spaces;
+        let parent = self.open.last_mut().unwrap();
+        if parent.list_has_blank_between
+            && let Some(Block::DefinitionList { tight, .. }) = parent.children.last_mut()
+        {
+            *tight = false;
+        }
+        self.open.push(
+            OpenBlock::new(OpenBlockType::DefinitionDetails { content_col })
+                .at(self.line_pos(ns_off)),
+        );
+        self.list_indent_sum += content_col;
+    }
+
+    /// Removes a `[!KIND]` line from the start of a block quote's first
+    /// paragraph, dropping the paragraph if nothing else is left in it.
+    fn take_alert_marker(&self, children: &mut Vec<Block>) -> Option<AlertKind> {
+        let Some(Block::Paragraph { raw, pos, .. }) = children.first_mut() else {
+            return None;
+        };
+        let (first, rest) = raw.split_once('\n').unwrap_or((raw, ""));
+        let kind = first
+            .trim_end()
+            .strip_prefix("[!")
+            .and_then(|m| m.strip_suffix(']'))
+            .and_then(AlertKind::from_marker)?;
+        if rest.is_empty() {
+            children.remove(0);
+            return Some(kind);
+        }
+        *raw = rest.to_string();
+        if let Some(pos) = pos {
+            pos.start = self.next_line_content_start(pos.start);
+        }
+        Some(kind)
+    }
+
+    /// Position of the first character of the line `lines` below `at`, past
+    /// its indentation and `>` markers. Used where link reference
+    /// definitions took the first lines of a paragraph.
+    fn skip_lines(&self, at: Position, lines: usize) -> Position {
+        if lines == 0 {
+            return at;
+        }
+        let bytes = self.input.as_bytes();
+        let mut line_start = at.offset;
+        for _ in 0..lines {
+            line_start += memchr::memchr(b'\n', &bytes[line_start..])
+                .map_or(bytes.len() - line_start, |i| i + 1);
+        }
+        let mut offset = line_start;
+        while matches!(bytes.get(offset), Some(b' ' | b'\t' | b'>')) {
+            offset += 1;
+        }
+        Position {
+            line: at.line + lines,
+            column: offset - line_start + 1,
+            offset,
+        }
+    }
+
+    /// Position of the first non-space character after the `>` marker on
+    /// the line following `at`.
+    fn next_line_content_start(&self, at: Position) -> Position {
+        let bytes = self.input.as_bytes();
+        let mut offset = at.offset;
+        while offset < bytes.len() && !matches!(bytes[offset], b'\n' | b'\r') {
+            offset += 1;
+        }
+        if bytes.get(offset) == Some(&b'\r') {
+            offset += 1;
+        }
+        if bytes.get(offset) == Some(&b'\n') {
+            offset += 1;
+        }
+        let line_start = offset;
+        while bytes.get(offset) == Some(&b' ') {
+            offset += 1;
+        }
+        if bytes.get(offset) == Some(&b'>') {
+            offset += 1;
+        }
+        while matches!(bytes.get(offset), Some(b' ' | b'\t')) {
+            offset += 1;
+        }
+        Position {
+            line: at.line + 1,
+            column: offset - line_start + 1,
+            offset,
+        }
+    }
+
+    /// Builds a heading, moving a trailing `{#id .class}` block out of `raw`
+    /// when attributes are enabled.
+    fn heading(&self, level: u8, mut raw: String, pos: Option<SourcePos>) -> Block {
+        let mut attrs = Attributes::new();
+        if self.enable_attributes
+            && let Some((text, parsed)) = split_trailing_attributes(&raw)
+        {
+            raw.truncate(text.len());
+            attrs = parsed;
+        }
+        Block::Heading {
+            level,
+            raw,
+            children: Vec::new(),
+            id: None,
+            attrs,
+            pos,
+        }
+    }
+
+    /// Splits a trailing attribute block off a fence's info string. As in
+    /// Pandoc, when the block is all there is (```` ```{.rust .numbered} ````)
+    /// its first class is the language.
+    fn code_info_attributes(&self, info: String) -> (String, Attributes) {
+        if !self.enable_attributes {
+            return (info, Attributes::new());
+        }
+        let Some((text, mut attrs)) = split_trailing_attributes(&info) else {
+            return (info, Attributes::new());
+        };
+        if !text.is_empty() {
+            return (text.to_string(), attrs);
+        }
+        let Some(classes) = attrs.remove("class") else {
+            return (String::new(), attrs);
+        };
+        let (lang, rest) = classes.split_once(' ').unwrap_or((&classes, ""));
+        if !rest.is_empty() {
+            attrs.insert("class".to_string(), rest.to_string());
+        }
+        (lang.to_string(), attrs)
+    }
+
     pub(super) fn finalize_block(&mut self, block: OpenBlock) -> Option<Block> {
+        let pos = self.block_pos(block.pos);
         match block.block_type {
             OpenBlockType::Document => Some(Block::Document {
                 children: block.children,
+                pos,
             }),
-            OpenBlockType::BlockQuote => Some(Block::BlockQuote {
-                children: block.children,
-            }),
+            OpenBlockType::BlockQuote => {
+                let mut children = block.children;
+                if self.enable_alerts
+                    && self.open.len() == 1
+                    && let Some(kind) = self.take_alert_marker(&mut children)
+                {
+                    return Some(Block::Alert {
+                        kind,
+                        children,
+                        pos,
+                    });
+                }
+                Some(Block::BlockQuote { children, pos })
+            }
             OpenBlockType::ListItem { .. } => {
                 let had_blank = block.had_blank_in_item;
                 let kind = block.list_kind.unwrap_or(ListKind::Bullet(b'-'));
@@ -642,6 +1049,7 @@ impl<'a> BlockParser<'a> {
                 let item = Block::ListItem {
                     children: block.children,
                     checked: block.checked,
+                    pos,
                 };
                 let parent = self.open.last_mut().unwrap();
 
@@ -656,10 +1064,14 @@ impl<'a> BlockParser<'a> {
                     kind: lk,
                     children: items,
                     tight,
+                    pos: list_pos,
                     ..
                 }) = parent.children.last_mut()
                     && *lk == kind
                 {
+                    if let (Some(list_pos), Some(pos)) = (list_pos, pos) {
+                        list_pos.end = pos.end;
+                    }
                     if parent.list_has_blank_between {
                         *tight = false;
                     }
@@ -680,13 +1092,28 @@ impl<'a> BlockParser<'a> {
                     start: block.list_start,
                     tight: !blank_between_children,
                     children: vec![item],
+                    pos,
                 };
                 Some(list)
             }
-            OpenBlockType::FencedCode(fc_data) => Some(Block::CodeBlock {
-                info: fc_data.info,
-                literal: block.content,
-            }),
+            OpenBlockType::FencedCode(fc_data) => {
+                let (info, attrs) = self.code_info_attributes(fc_data.info);
+                let is_math = fc_data.fence_char == b'$'
+                    || (self.enable_math && info.split_whitespace().next() == Some("math"));
+                if is_math {
+                    let mut literal = block.content;
+                    if literal.ends_with('\n') {
+                        literal.pop();
+                    }
+                    return Some(Block::MathBlock { literal, pos });
+                }
+                Some(Block::CodeBlock {
+                    info,
+                    literal: block.content,
+                    attrs,
+                    pos,
+                })
+            }
             OpenBlockType::IndentedCode => {
                 let mut literal = block.content;
                 literal.push('\n');
@@ -695,25 +1122,74 @@ impl<'a> BlockParser<'a> {
                 Some(Block::CodeBlock {
                     info: String::new(),
                     literal,
+                    attrs: Attributes::new(),
+                    pos,
                 })
             }
             OpenBlockType::HtmlBlock { .. } => Some(Block::HtmlBlock {
                 literal: block.content,
+                pos,
             }),
             OpenBlockType::Table(td) => Some(Block::Table(Box::new(crate::ast::TableData {
                 alignments: td.alignments,
                 header: td.header,
                 rows: td.rows,
+                pos,
             }))),
+            OpenBlockType::Container(data) => Some(Block::Container {
+                name: data.name,
+                attributes: data.attributes,
+                children: block.children,
+                pos,
+            }),
+            OpenBlockType::DefinitionDetails { .. } => {
+                let had_blank = block.had_blank_in_item;
+                let blank_between_children = had_blank && block.children.len() >= 2;
+                let details = Block::DefinitionDetails {
+                    children: block.children,
+                    pos,
+                };
+                let parent = self.open.last_mut().unwrap();
+                parent.list_has_blank_between = had_blank;
+                if let Some(Block::DefinitionList {
+                    tight,
+                    children,
+                    pos: list_pos,
+                }) = parent.children.last_mut()
+                {
+                    if let (Some(list_pos), Some(pos)) = (list_pos, pos) {
+                        list_pos.end = pos.end;
+                    }
+                    if blank_between_children {
+                        *tight = false;
+                    }
+                    children.push(details);
+                }
+                None
+            }
+            OpenBlockType::FootnoteDefinition { label } => Some(Block::FootnoteDefinition {
+                label,
+                children: block.children,
+                pos,
+            }),
             OpenBlockType::Paragraph => {
                 if block.content.is_empty() {
                     return None;
                 }
+                let lines = self.source_positions.then(|| line_count(&block.content));
                 let remaining = self.extract_ref_defs_owned(block.content);
                 if remaining.is_empty() {
                     return None;
                 }
-                Some(Block::Paragraph { raw: remaining })
+                let mut pos = pos;
+                if let (Some(pos), Some(lines)) = (&mut pos, lines) {
+                    pos.start = self.skip_lines(pos.start, lines - line_count(&remaining));
+                }
+                Some(Block::Paragraph {
+                    raw: remaining,
+                    children: Vec::new(),
+                    pos,
+                })
             }
         }
     }
@@ -0,0 +1,1074 @@
+pub(crate) static EMOJI: &[(&str, &str)] = &[
+    ("+1", "\u{1F44D}"),
+    ("-1", "\u{1F44E}"),
+    ("100", "\u{1F4AF}"),
+    ("1234", "\u{1F522}"),
+    ("1st_place_medal", "\u{1F947}"),
+    ("2nd_place_medal", "\u{1F948}"),
+    ("3rd_place_medal", "\u{1F949}"),
+    ("8ball", "\u{1F3B1}"),
+    ("a", "\u{1F170}\u{FE0F}"),
+    ("ab", "\u{1F18E}"),
+    ("abacus", "\u{1F9EE}"),
+    ("abc", "\u{1F524}"),
+    ("abcd", "\u{1F521}"),
+    ("adhesive_bandage", "\u{1FA79}"),
+    ("adult", "\u{1F9D1}"),
+    ("airplane", "\u{2708}\u{FE0F}"),
+    ("alarm_clock", "\u{23F0}"),
+    ("alembic", "\u{2697}\u{FE0F}"),
+    ("alien", "\u{1F47D}"),
+    ("ambulance", "\u{1F691}"),
+    ("amphora", "\u{1F3FA}"),
+    ("anchor", "\u{2693}"),
+    ("angel", "\u{1F47C}"),
+    ("anger", "\u{1F4A2}"),
+    ("angry", "\u{1F620}"),
+    ("anguished", "\u{1F627}"),
+    ("ant", "\u{1F41C}"),
+    ("apple", "\u{1F34E}"),
+    ("aquarius", "\u{2652}"),
+    ("aries", "\u{2648}"),
+    ("arrow_backward", "\u{25C0}\u{FE0F}"),
+    ("arrow_double_down", "\u{23EC}"),
+    ("arrow_double_up", "\u{23EB}"),
+    ("arrow_down", "\u{2B07}\u{FE0F}"),
+    ("arrow_down_small", "\u{1F53D}"),
+    ("arrow_forward", "\u{25B6}\u{FE0F}"),
+    ("arrow_heading_down", "\u{2935}\u{FE0F}"),
+    ("arrow_heading_up", "\u{2934}\u{FE0F}"),
+    ("arrow_left", "\u{2B05}\u{FE0F}"),
+    ("arrow_lower_left", "\u{2199}\u{FE0F}"),
+    ("arrow_lower_right", "\u{2198}\u{FE0F}"),
+    ("arrow_right", "\u{27A1}\u{FE0F}"),
+    ("arrow_right_hook", "\u{21AA}\u{FE0F}"),
+    ("arrow_up", "\u{2B06}\u{FE0F}"),
+    ("arrow_up_down", "\u{2195}\u{FE0F}"),
+    ("arrow_up_small", "\u{1F53C}"),
+    ("arrow_upper_left", "\u{2196}\u{FE0F}"),
+    ("arrow_upper_right", "\u{2197}\u{FE0F}"),
+    ("arrows_clockwise", "\u{1F503}"),
+    ("arrows_counterclockwise", "\u{1F504}"),
+    ("art", "\u{1F3A8}"),
+    ("astonished", "\u{1F632}"),
+    ("athletic_shoe", "\u{1F45F}"),
+    ("atm", "\u{1F3E7}"),
+    ("atom_symbol", "\u{269B}\u{FE0F}"),
+    ("avocado", "\u{1F951}"),
+    ("axe", "\u{1FA93}"),
+    ("b", "\u{1F171}\u{FE0F}"),
+    ("baby", "\u{1F476}"),
+    ("baby_bottle", "\u{1F37C}"),
+    ("baby_chick", "\u{1F424}"),
+    ("baby_symbol", "\u{1F6BC}"),
+    ("back", "\u{1F519}"),
+    ("bacon", "\u{1F953}"),
+    ("badger", "\u{1F9A1}"),
+    ("badminton", "\u{1F3F8}"),
+    ("bagel", "\u{1F96F}"),
+    ("baguette_bread", "\u{1F956}"),
+    ("balance_scale", "\u{2696}\u{FE0F}"),
+    ("balloon", "\u{1F388}"),
+    ("ballot_box", "\u{1F5F3}\u{FE0F}"),
+    ("ballot_box_with_check", "\u{2611}\u{FE0F}"),
+    ("bamboo", "\u{1F38D}"),
+    ("banana", "\u{1F34C}"),
+    ("bangbang", "\u{203C}\u{FE0F}"),
+    ("banjo", "\u{1FA95}"),
+    ("bank", "\u{1F3E6}"),
+    ("bar_chart", "\u{1F4CA}"),
+    ("baseball", "\u{26BE}"),
+    ("basket", "\u{1F9FA}"),
+    ("basketball", "\u{1F3C0}"),
+    ("bat", "\u{1F987}"),
+    ("bathtub", "\u{1F6C1}"),
+    ("battery", "\u{1F50B}"),
+    ("bear", "\u{1F43B}"),
+    ("bed", "\u{1F6CF}\u{FE0F}"),
+    ("bee", "\u{1F41D}"),
+    ("beer", "\u{1F37A}"),
+    ("beers", "\u{1F37B}"),
+    ("beetle", "\u{1F41E}"),
+    ("beginner", "\u{1F530}"),
+    ("bell", "\u{1F514}"),
+    ("bento", "\u{1F371}"),
+    ("beverage_box", "\u{1F9C3}"),
+    ("bike", "\u{1F6B2}"),
+    ("bikini", "\u{1F459}"),
+    ("billed_cap", "\u{1F9E2}"),
+    ("biohazard", "\u{2623}\u{FE0F}"),
+    ("bird", "\u{1F426}"),
+    ("birthday", "\u{1F382}"),
+    ("black_circle", "\u{26AB}"),
+    ("black_flag", "\u{1F3F4}"),
+    ("black_heart", "\u{1F5A4}"),
+    ("black_joker", "\u{1F0CF}"),
+    ("black_large_square", "\u{2B1B}"),
+    ("black_nib", "\u{2712}\u{FE0F}"),
+    ("black_square_button", "\u{1F532}"),
+    ("blossom", "\u{1F33C}"),
+    ("blowfish", "\u{1F421}"),
+    ("blue_book", "\u{1F4D8}"),
+    ("blue_car", "\u{1F699}"),
+    ("blue_heart", "\u{1F499}"),
+    ("blue_square", "\u{1F7E6}"),
+    ("blush", "\u{1F60A}"),
+    ("boar", "\u{1F417}"),
+    ("boat", "\u{26F5}"),
+    ("bomb", "\u{1F4A3}"),
+    ("book", "\u{1F4D6}"),
+    ("bookmark", "\u{1F516}"),
+    ("bookmark_tabs", "\u{1F4D1}"),
+    ("books", "\u{1F4DA}"),
+    ("boom", "\u{1F4A5}"),
+    ("boot", "\u{1F462}"),
+    ("bouquet", "\u{1F490}"),
+    ("bow", "\u{1F647}"),
+    ("bow_and_arrow", "\u{1F3F9}"),
+    ("bowl_with_spoon", "\u{1F963}"),
+    ("bowling", "\u{1F3B3}"),
+    ("boxing_glove", "\u{1F94A}"),
+    ("boy", "\u{1F466}"),
+    ("brain", "\u{1F9E0}"),
+    ("bread", "\u{1F35E}"),
+    ("bridge_at_night", "\u{1F309}"),
+    ("briefcase", "\u{1F4BC}"),
+    ("broccoli", "\u{1F966}"),
+    ("broken_heart", "\u{1F494}"),
+    ("broom", "\u{1F9F9}"),
+    ("brown_circle", "\u{1F7E4}"),
+    ("brown_heart", "\u{1F90E}"),
+    ("brown_square", "\u{1F7EB}"),
+    ("bug", "\u{1F41B}"),
+    ("bulb", "\u{1F4A1}"),
+    ("bullettrain_front", "\u{1F685}"),
+    ("bullettrain_side", "\u{1F684}"),
+    ("burrito", "\u{1F32F}"),
+    ("bus", "\u{1F68C}"),
+    ("busstop", "\u{1F68F}"),
+    ("bust_in_silhouette", "\u{1F464}"),
+    ("busts_in_silhouette", "\u{1F465}"),
+    ("butter", "\u{1F9C8}"),
+    ("butterfly", "\u{1F98B}"),
+    ("cactus", "\u{1F335}"),
+    ("cake", "\u{1F370}"),
+    ("calendar", "\u{1F4C6}"),
+    ("call_me_hand", "\u{1F919}"),
+    ("calling", "\u{1F4F2}"),
+    ("camel", "\u{1F42B}"),
+    ("camera", "\u{1F4F7}"),
+    ("camera_flash", "\u{1F4F8}"),
+    ("cancer", "\u{264B}"),
+    ("candle", "\u{1F56F}\u{FE0F}"),
+    ("candy", "\u{1F36C}"),
+    ("canned_food", "\u{1F96B}"),
+    ("canoe", "\u{1F6F6}"),
+    ("capital_abcd", "\u{1F520}"),
+    ("capricorn", "\u{2651}"),
+    ("car", "\u{1F697}"),
+    ("card_file_box", "\u{1F5C3}\u{FE0F}"),
+    ("card_index", "\u{1F4C7}"),
+    ("card_index_dividers", "\u{1F5C2}\u{FE0F}"),
+    ("carousel_horse", "\u{1F3A0}"),
+    ("carrot", "\u{1F955}"),
+    ("cat", "\u{1F431}"),
+    ("cat2", "\u{1F408}"),
+    ("cd", "\u{1F4BF}"),
+    ("chains", "\u{26D3}\u{FE0F}"),
+    ("chair", "\u{1FA91}"),
+    ("champagne", "\u{1F37E}"),
+    ("chart", "\u{1F4B9}"),
+    ("chart_with_downwards_trend", "\u{1F4C9}"),
+    ("chart_with_upwards_trend", "\u{1F4C8}"),
+    ("checkered_flag", "\u{1F3C1}"),
+    ("cheese", "\u{1F9C0}"),
+    ("cherries", "\u{1F352}"),
+    ("cherry_blossom", "\u{1F338}"),
+    ("chess_pawn", "\u{265F}\u{FE0F}"),
+    ("chestnut", "\u{1F330}"),
+    ("chicken", "\u{1F414}"),
+    ("child", "\u{1F9D2}"),
+    ("children_crossing", "\u{1F6B8}"),
+    ("chipmunk", "\u{1F43F}\u{FE0F}"),
+    ("chocolate_bar", "\u{1F36B}"),
+    ("chopsticks", "\u{1F962}"),
+    ("christmas_tree", "\u{1F384}"),
+    ("church", "\u{26EA}"),
+    ("cinema", "\u{1F3A6}"),
+    ("circus_tent", "\u{1F3AA}"),
+    ("city_sunset", "\u{1F306}"),
+    ("cl", "\u{1F191}"),
+    ("clamp", "\u{1F5DC}\u{FE0F}"),
+    ("clap", "\u{1F44F}"),
+    ("clapper", "\u{1F3AC}"),
+    ("clinking_glasses", "\u{1F942}"),
+    ("clipboard", "\u{1F4CB}"),
+    ("clock1", "\u{1F550}"),
+    ("clock12", "\u{1F55B}"),
+    ("closed_book", "\u{1F4D5}"),
+    ("closed_lock_with_key", "\u{1F510}"),
+    ("closed_umbrella", "\u{1F302}"),
+    ("cloud", "\u{2601}\u{FE0F}"),
+    ("cloud_with_lightning_and_rain", "\u{26C8}\u{FE0F}"),
+    ("cloud_with_rain", "\u{1F327}\u{FE0F}"),
+    ("cloud_with_snow", "\u{1F328}\u{FE0F}"),
+    ("clown_face", "\u{1F921}"),
+    ("clubs", "\u{2663}\u{FE0F}"),
+    ("coat", "\u{1F9E5}"),
+    ("cocktail", "\u{1F378}"),
+    ("coconut", "\u{1F965}"),
+    ("coffee", "\u{2615}"),
+    ("coffin", "\u{26B0}\u{FE0F}"),
+    ("cold_face", "\u{1F976}"),
+    ("cold_sweat", "\u{1F630}"),
+    ("collision", "\u{1F4A5}"),
+    ("comet", "\u{2604}\u{FE0F}"),
+    ("compass", "\u{1F9ED}"),
+    ("computer", "\u{1F4BB}"),
+    ("computer_mouse", "\u{1F5B1}\u{FE0F}"),
+    ("confetti_ball", "\u{1F38A}"),
+    ("confounded", "\u{1F616}"),
+    ("confused", "\u{1F615}"),
+    ("construction", "\u{1F6A7}"),
+    ("construction_worker", "\u{1F477}"),
+    ("cookie", "\u{1F36A}"),
+    ("cool", "\u{1F192}"),
+    ("cop", "\u{1F46E}"),
+    ("copyright", "\u{A9}\u{FE0F}"),
+    ("corn", "\u{1F33D}"),
+    ("couch_and_lamp", "\u{1F6CB}\u{FE0F}"),
+    ("couple", "\u{1F46B}"),
+    ("cow", "\u{1F42E}"),
+    ("cow2", "\u{1F404}"),
+    ("cowboy_hat_face", "\u{1F920}"),
+    ("crab", "\u{1F980}"),
+    ("crayon", "\u{1F58D}\u{FE0F}"),
+    ("credit_card", "\u{1F4B3}"),
+    ("crescent_moon", "\u{1F319}"),
+    ("cricket", "\u{1F997}"),
+    ("crocodile", "\u{1F40A}"),
+    ("croissant", "\u{1F950}"),
+    ("crossed_fingers", "\u{1F91E}"),
+    ("crossed_flags", "\u{1F38C}"),
+    ("crossed_swords", "\u{2694}\u{FE0F}"),
+    ("crown", "\u{1F451}"),
+    ("cry", "\u{1F622}"),
+    ("crying_cat_face", "\u{1F63F}"),
+    ("crystal_ball", "\u{1F52E}"),
+    ("cucumber", "\u{1F952}"),
+    ("cup_with_straw", "\u{1F964}"),
+    ("cupcake", "\u{1F9C1}"),
+    ("cupid", "\u{1F498}"),
+    ("curling_stone", "\u{1F94C}"),
+    ("curly_loop", "\u{27B0}"),
+    ("currency_exchange", "\u{1F4B1}"),
+    ("curry", "\u{1F35B}"),
+    ("cursing_face", "\u{1F92C}"),
+    ("custard", "\u{1F36E}"),
+    ("cut_of_meat", "\u{1F969}"),
+    ("cyclone", "\u{1F300}"),
+    ("dagger", "\u{1F5E1}\u{FE0F}"),
+    ("dancer", "\u{1F483}"),
+    ("dancers", "\u{1F46F}"),
+    ("dango", "\u{1F361}"),
+    ("dark_sunglasses", "\u{1F576}\u{FE0F}"),
+    ("dart", "\u{1F3AF}"),
+    ("dash", "\u{1F4A8}"),
+    ("date", "\u{1F4C5}"),
+    ("deciduous_tree", "\u{1F333}"),
+    ("deer", "\u{1F98C}"),
+    ("desktop_computer", "\u{1F5A5}\u{FE0F}"),
+    ("detective", "\u{1F575}\u{FE0F}"),
+    ("diamond_shape_with_a_dot_inside", "\u{1F4A0}"),
+    ("diamonds", "\u{2666}\u{FE0F}"),
+    ("disappointed", "\u{1F61E}"),
+    ("disappointed_relieved", "\u{1F625}"),
+    ("diya_lamp", "\u{1FA94}"),
+    ("dizzy", "\u{1F4AB}"),
+    ("dizzy_face", "\u{1F635}"),
+    ("dna", "\u{1F9EC}"),
+    ("do_not_litter", "\u{1F6AF}"),
+    ("dog", "\u{1F436}"),
+    ("dog2", "\u{1F415}"),
+    ("dollar", "\u{1F4B5}"),
+    ("dolls", "\u{1F38E}"),
+    ("dolphin", "\u{1F42C}"),
+    ("door", "\u{1F6AA}"),
+    ("doughnut", "\u{1F369}"),
+    ("dove", "\u{1F54A}\u{FE0F}"),
+    ("dragon", "\u{1F409}"),
+    ("dragon_face", "\u{1F432}"),
+    ("dress", "\u{1F457}"),
+    ("dromedary_camel", "\u{1F42A}"),
+    ("drooling_face", "\u{1F924}"),
+    ("drop_of_blood", "\u{1FA78}"),
+    ("droplet", "\u{1F4A7}"),
+    ("drum", "\u{1F941}"),
+    ("duck", "\u{1F986}"),
+    ("dumpling", "\u{1F95F}"),
+    ("dvd", "\u{1F4C0}"),
+    ("e-mail", "\u{1F4E7}"),
+    ("eagle", "\u{1F985}"),
+    ("ear", "\u{1F442}"),
+    ("ear_of_rice", "\u{1F33E}"),
+    ("earth_africa", "\u{1F30D}"),
+    ("earth_americas", "\u{1F30E}"),
+    ("earth_asia", "\u{1F30F}"),
+    ("egg", "\u{1F95A}"),
+    ("eggplant", "\u{1F346}"),
+    ("eight_pointed_black_star", "\u{2734}\u{FE0F}"),
+    ("eight_spoked_asterisk", "\u{2733}\u{FE0F}"),
+    ("electric_plug", "\u{1F50C}"),
+    ("elephant", "\u{1F418}"),
+    ("email", "\u{2709}\u{FE0F}"),
+    ("end", "\u{1F51A}"),
+    ("envelope", "\u{2709}\u{FE0F}"),
+    ("envelope_with_arrow", "\u{1F4E9}"),
+    ("euro", "\u{1F4B6}"),
+    ("european_castle", "\u{1F3F0}"),
+    ("evergreen_tree", "\u{1F332}"),
+    ("exclamation", "\u{2757}"),
+    ("exploding_head", "\u{1F92F}"),
+    ("expressionless", "\u{1F611}"),
+    ("eye", "\u{1F441}\u{FE0F}"),
+    ("eyeglasses", "\u{1F453}"),
+    ("eyes", "\u{1F440}"),
+    ("face_with_head_bandage", "\u{1F915}"),
+    ("face_with_thermometer", "\u{1F912}"),
+    ("facepalm", "\u{1F926}"),
+    ("facepunch", "\u{1F44A}"),
+    ("factory", "\u{1F3ED}"),
+    ("fairy", "\u{1F9DA}"),
+    ("falafel", "\u{1F9C6}"),
+    ("fallen_leaf", "\u{1F342}"),
+    ("family", "\u{1F46A}"),
+    ("fast_forward", "\u{23E9}"),
+    ("fax", "\u{1F4E0}"),
+    ("fearful", "\u{1F628}"),
+    ("feet", "\u{1F43E}"),
+    ("female_sign", "\u{2640}\u{FE0F}"),
+    ("ferris_wheel", "\u{1F3A1}"),
+    ("file_cabinet", "\u{1F5C4}\u{FE0F}"),
+    ("file_folder", "\u{1F4C1}"),
+    ("film_projector", "\u{1F4FD}\u{FE0F}"),
+    ("film_strip", "\u{1F39E}\u{FE0F}"),
+    ("fire", "\u{1F525}"),
+    ("fire_engine", "\u{1F692}"),
+    ("fire_extinguisher", "\u{1F9EF}"),
+    ("fireworks", "\u{1F386}"),
+    ("fish", "\u{1F41F}"),
+    ("fish_cake", "\u{1F365}"),
+    ("fishing_pole_and_fish", "\u{1F3A3}"),
+    ("fist", "\u{270A}"),
+    ("fist_left", "\u{1F91B}"),
+    ("fist_oncoming", "\u{1F44A}"),
+    ("fist_raised", "\u{270A}"),
+    ("fist_right", "\u{1F91C}"),
+    ("flags", "\u{1F38F}"),
+    ("flamingo", "\u{1F9A9}"),
+    ("flashlight", "\u{1F526}"),
+    ("fleur_de_lis", "\u{269C}\u{FE0F}"),
+    ("flipper", "\u{1F42C}"),
+    ("floppy_disk", "\u{1F4BE}"),
+    ("flushed", "\u{1F633}"),
+    ("flying_saucer", "\u{1F6F8}"),
+    ("fog", "\u{1F32B}\u{FE0F}"),
+    ("foggy", "\u{1F301}"),
+    ("football", "\u{1F3C8}"),
+    ("footprints", "\u{1F463}"),
+    ("fork_and_knife", "\u{1F374}"),
+    ("fortune_cookie", "\u{1F960}"),
+    ("fountain", "\u{26F2}"),
+    ("fountain_pen", "\u{1F58B}\u{FE0F}"),
+    ("four_leaf_clover", "\u{1F340}"),
+    ("fox_face", "\u{1F98A}"),
+    ("framed_picture", "\u{1F5BC}\u{FE0F}"),
+    ("free", "\u{1F193}"),
+    ("fried_egg", "\u{1F373}"),
+    ("fried_shrimp", "\u{1F364}"),
+    ("fries", "\u{1F35F}"),
+    ("frog", "\u{1F438}"),
+    ("frowning", "\u{1F626}"),
+    ("frowning_face", "\u{2639}\u{FE0F}"),
+    ("fu", "\u{1F595}"),
+    ("fuelpump", "\u{26FD}"),
+    ("full_moon", "\u{1F315}"),
+    ("game_die", "\u{1F3B2}"),
+    ("garlic", "\u{1F9C4}"),
+    ("gear", "\u{2699}\u{FE0F}"),
+    ("gem", "\u{1F48E}"),
+    ("gemini", "\u{264A}"),
+    ("ghost", "\u{1F47B}"),
+    ("gift", "\u{1F381}"),
+    ("gift_heart", "\u{1F49D}"),
+    ("giraffe", "\u{1F992}"),
+    ("girl", "\u{1F467}"),
+    ("globe_with_meridians", "\u{1F310}"),
+    ("gloves", "\u{1F9E4}"),
+    ("goal_net", "\u{1F945}"),
+    ("goat", "\u{1F410}"),
+    ("goggles", "\u{1F97D}"),
+    ("golf", "\u{26F3}"),
+    ("gorilla", "\u{1F98D}"),
+    ("grapes", "\u{1F347}"),
+    ("green_apple", "\u{1F34F}"),
+    ("green_book", "\u{1F4D7}"),
+    ("green_circle", "\u{1F7E2}"),
+    ("green_heart", "\u{1F49A}"),
+    ("green_salad", "\u{1F957}"),
+    ("green_square", "\u{1F7E9}"),
+    ("grey_exclamation", "\u{2755}"),
+    ("grey_question", "\u{2754}"),
+    ("grimacing", "\u{1F62C}"),
+    ("grin", "\u{1F601}"),
+    ("grinning", "\u{1F600}"),
+    ("guardsman", "\u{1F482}"),
+    ("guitar", "\u{1F3B8}"),
+    ("gun", "\u{1F52B}"),
+    ("haircut", "\u{1F487}"),
+    ("hamburger", "\u{1F354}"),
+    ("hammer", "\u{1F528}"),
+    ("hammer_and_pick", "\u{2692}\u{FE0F}"),
+    ("hammer_and_wrench", "\u{1F6E0}\u{FE0F}"),
+    ("hamster", "\u{1F439}"),
+    ("hand", "\u{270B}"),
+    ("hand_over_mouth", "\u{1F92D}"),
+    ("handbag", "\u{1F45C}"),
+    ("handshake", "\u{1F91D}"),
+    ("hankey", "\u{1F4A9}"),
+    ("hatched_chick", "\u{1F425}"),
+    ("hatching_chick", "\u{1F423}"),
+    ("headphones", "\u{1F3A7}"),
+    ("hear_no_evil", "\u{1F649}"),
+    ("heart", "\u{2764}\u{FE0F}"),
+    ("heart_decoration", "\u{1F49F}"),
+    ("heart_eyes", "\u{1F60D}"),
+    ("heart_eyes_cat", "\u{1F63B}"),
+    ("heartbeat", "\u{1F493}"),
+    ("heartpulse", "\u{1F497}"),
+    ("hearts", "\u{2665}\u{FE0F}"),
+    ("heavy_check_mark", "\u{2714}\u{FE0F}"),
+    ("heavy_division_sign", "\u{2797}"),
+    ("heavy_dollar_sign", "\u{1F4B2}"),
+    ("heavy_exclamation_mark", "\u{2757}"),
+    ("heavy_heart_exclamation", "\u{2763}\u{FE0F}"),
+    ("heavy_minus_sign", "\u{2796}"),
+    ("heavy_multiplication_x", "\u{2716}\u{FE0F}"),
+    ("heavy_plus_sign", "\u{2795}"),
+    ("hedgehog", "\u{1F994}"),
+    ("helicopter", "\u{1F681}"),
+    ("herb", "\u{1F33F}"),
+    ("hibiscus", "\u{1F33A}"),
+    ("high_brightness", "\u{1F506}"),
+    ("high_heel", "\u{1F460}"),
+    ("hippopotamus", "\u{1F99B}"),
+    ("hocho", "\u{1F52A}"),
+    ("hole", "\u{1F573}\u{FE0F}"),
+    ("honey_pot", "\u{1F36F}"),
+    ("honeybee", "\u{1F41D}"),
+    ("horse", "\u{1F434}"),
+    ("hospital", "\u{1F3E5}"),
+    ("hot_face", "\u{1F975}"),
+    ("hot_pepper", "\u{1F336}\u{FE0F}"),
+    ("hotdog", "\u{1F32D}"),
+    ("hotel", "\u{1F3E8}"),
+    ("hourglass", "\u{231B}"),
+    ("hourglass_flowing_sand", "\u{23F3}"),
+    ("house", "\u{1F3E0}"),
+    ("house_with_garden", "\u{1F3E1}"),
+    ("hugs", "\u{1F917}"),
+    ("hushed", "\u{1F62F}"),
+    ("ice_cream", "\u{1F368}"),
+    ("ice_cube", "\u{1F9CA}"),
+    ("ice_skate", "\u{26F8}\u{FE0F}"),
+    ("icecream", "\u{1F366}"),
+    ("id", "\u{1F194}"),
+    ("imp", "\u{1F47F}"),
+    ("inbox_tray", "\u{1F4E5}"),
+    ("incoming_envelope", "\u{1F4E8}"),
+    ("infinity", "\u{267E}\u{FE0F}"),
+    ("information_source", "\u{2139}\u{FE0F}"),
+    ("innocent", "\u{1F607}"),
+    ("interrobang", "\u{2049}\u{FE0F}"),
+    ("iphone", "\u{1F4F1}"),
+    ("izakaya_lantern", "\u{1F3EE}"),
+    ("jack_o_lantern", "\u{1F383}"),
+    ("japan", "\u{1F5FE}"),
+    ("japanese_goblin", "\u{1F47A}"),
+    ("japanese_ogre", "\u{1F479}"),
+    ("jeans", "\u{1F456}"),
+    ("jigsaw", "\u{1F9E9}"),
+    ("joy", "\u{1F602}"),
+    ("joy_cat", "\u{1F639}"),
+    ("joystick", "\u{1F579}\u{FE0F}"),
+    ("kangaroo", "\u{1F998}"),
+    ("key", "\u{1F511}"),
+    ("keyboard", "\u{2328}\u{FE0F}"),
+    ("kick_scooter", "\u{1F6F4}"),
+    ("kimono", "\u{1F458}"),
+    ("kiss", "\u{1F48B}"),
+    ("kissing", "\u{1F617}"),
+    ("kissing_heart", "\u{1F618}"),
+    ("kite", "\u{1FA81}"),
+    ("kiwi_fruit", "\u{1F95D}"),
+    ("knife", "\u{1F52A}"),
+    ("koala", "\u{1F428}"),
+    ("lab_coat", "\u{1F97C}"),
+    ("label", "\u{1F3F7}\u{FE0F}"),
+    ("lady_beetle", "\u{1F41E}"),
+    ("lantern", "\u{1F3EE}"),
+    ("large_blue_circle", "\u{1F535}"),
+    ("large_blue_diamond", "\u{1F537}"),
+    ("large_orange_diamond", "\u{1F536}"),
+    ("latin_cross", "\u{271D}\u{FE0F}"),
+    ("laughing", "\u{1F606}"),
+    ("leafy_green", "\u{1F96C}"),
+    ("leaves", "\u{1F343}"),
+    ("ledger", "\u{1F4D2}"),
+    ("left_right_arrow", "\u{2194}\u{FE0F}"),
+    ("leftwards_arrow_with_hook", "\u{21A9}\u{FE0F}"),
+    ("lemon", "\u{1F34B}"),
+    ("leo", "\u{264C}"),
+    ("leopard", "\u{1F406}"),
+    ("libra", "\u{264E}"),
+    ("link", "\u{1F517}"),
+    ("lion", "\u{1F981}"),
+    ("lips", "\u{1F444}"),
+    ("lipstick", "\u{1F484}"),
+    ("lizard", "\u{1F98E}"),
+    ("llama", "\u{1F999}"),
+    ("lobster", "\u{1F99E}"),
+    ("lock", "\u{1F512}"),
+    ("lock_with_ink_pen", "\u{1F50F}"),
+    ("lollipop", "\u{1F36D}"),
+    ("loop", "\u{27BF}"),
+    ("lotion_bottle", "\u{1F9F4}"),
+    ("loud_sound", "\u{1F50A}"),
+    ("loudspeaker", "\u{1F4E2}"),
+    ("love_letter", "\u{1F48C}"),
+    ("love_you_gesture", "\u{1F91F}"),
+    ("low_brightness", "\u{1F505}"),
+    ("lying_face", "\u{1F925}"),
+    ("m", "\u{24C2}\u{FE0F}"),
+    ("mag", "\u{1F50D}"),
+    ("mag_right", "\u{1F50E}"),
+    ("mage", "\u{1F9D9}"),
+    ("magnet", "\u{1F9F2}"),
+    ("mahjong", "\u{1F004}"),
+    ("mailbox", "\u{1F4EB}"),
+    ("mailbox_closed", "\u{1F4EA}"),
+    ("mailbox_with_mail", "\u{1F4EC}"),
+    ("mailbox_with_no_mail", "\u{1F4ED}"),
+    ("male_sign", "\u{2642}\u{FE0F}"),
+    ("man", "\u{1F468}"),
+    ("man_dancing", "\u{1F57A}"),
+    ("mango", "\u{1F96D}"),
+    ("mans_shoe", "\u{1F45E}"),
+    ("maple_leaf", "\u{1F341}"),
+    ("martial_arts_uniform", "\u{1F94B}"),
+    ("mask", "\u{1F637}"),
+    ("massage", "\u{1F486}"),
+    ("mate", "\u{1F9C9}"),
+    ("meat_on_bone", "\u{1F356}"),
+    ("medal_military", "\u{1F396}\u{FE0F}"),
+    ("medal_sports", "\u{1F3C5}"),
+    ("mega", "\u{1F4E3}"),
+    ("melon", "\u{1F348}"),
+    ("memo", "\u{1F4DD}"),
+    ("menorah", "\u{1F54E}"),
+    ("mens", "\u{1F6B9}"),
+    ("metal", "\u{1F918}"),
+    ("metro", "\u{1F687}"),
+    ("microbe", "\u{1F9A0}"),
+    ("microphone", "\u{1F3A4}"),
+    ("microscope", "\u{1F52C}"),
+    ("middle_finger", "\u{1F595}"),
+    ("milk_glass", "\u{1F95B}"),
+    ("milky_way", "\u{1F30C}"),
+    ("minidisc", "\u{1F4BD}"),
+    ("mobile_phone_off", "\u{1F4F4}"),
+    ("money_mouth_face", "\u{1F911}"),
+    ("money_with_wings", "\u{1F4B8}"),
+    ("moneybag", "\u{1F4B0}"),
+    ("monkey", "\u{1F412}"),
+    ("monkey_face", "\u{1F435}"),
+    ("monocle_face", "\u{1F9D0}"),
+    ("moon_cake", "\u{1F96E}"),
+    ("mortar_board", "\u{1F393}"),
+    ("mosquito", "\u{1F99F}"),
+    ("mount_fuji", "\u{1F5FB}"),
+    ("mouse", "\u{1F42D}"),
+    ("mouse2", "\u{1F401}"),
+    ("movie_camera", "\u{1F3A5}"),
+    ("moyai", "\u{1F5FF}"),
+    ("muscle", "\u{1F4AA}"),
+    ("mushroom", "\u{1F344}"),
+    ("musical_keyboard", "\u{1F3B9}"),
+    ("musical_note", "\u{1F3B5}"),
+    ("musical_score", "\u{1F3BC}"),
+    ("mute", "\u{1F507}"),
+    ("nail_care", "\u{1F485}"),
+    ("name_badge", "\u{1F4DB}"),
+    ("nauseated_face", "\u{1F922}"),
+    ("nazar_amulet", "\u{1F9FF}"),
+    ("necktie", "\u{1F454}"),
+    ("negative_squared_cross_mark", "\u{274E}"),
+    ("nerd_face", "\u{1F913}"),
+    ("neutral_face", "\u{1F610}"),
+    ("new", "\u{1F195}"),
+    ("new_moon", "\u{1F311}"),
+    ("newspaper", "\u{1F4F0}"),
+    ("newspaper_roll", "\u{1F5DE}\u{FE0F}"),
+    ("ng", "\u{1F196}"),
+    ("night_with_stars", "\u{1F303}"),
+    ("no_bell", "\u{1F515}"),
+    ("no_bicycles", "\u{1F6B3}"),
+    ("no_entry", "\u{26D4}"),
+    ("no_entry_sign", "\u{1F6AB}"),
+    ("no_good", "\u{1F645}"),
+    ("no_mobile_phones", "\u{1F4F5}"),
+    ("no_mouth", "\u{1F636}"),
+    ("no_pedestrians", "\u{1F6B7}"),
+    ("no_smoking", "\u{1F6AD}"),
+    ("nose", "\u{1F443}"),
+    ("notebook", "\u{1F4D3}"),
+    ("notebook_with_decorative_cover", "\u{1F4D4}"),
+    ("notes", "\u{1F3B6}"),
+    ("nut_and_bolt", "\u{1F529}"),
+    ("o", "\u{2B55}"),
+    ("o2", "\u{1F17E}\u{FE0F}"),
+    ("ocean", "\u{1F30A}"),
+    ("octopus", "\u{1F419}"),
+    ("oden", "\u{1F362}"),
+    ("office", "\u{1F3E2}"),
+    ("ok", "\u{1F197}"),
+    ("ok_hand", "\u{1F44C}"),
+    ("ok_woman", "\u{1F646}"),
+    ("old_key", "\u{1F5DD}\u{FE0F}"),
+    ("older_adult", "\u{1F9D3}"),
+    ("older_man", "\u{1F474}"),
+    ("older_woman", "\u{1F475}"),
+    ("om", "\u{1F549}\u{FE0F}"),
+    ("on", "\u{1F51B}"),
+    ("onion", "\u{1F9C5}"),
+    ("open_book", "\u{1F4D6}"),
+    ("open_file_folder", "\u{1F4C2}"),
+    ("open_hands", "\u{1F450}"),
+    ("open_mouth", "\u{1F62E}"),
+    ("ophiuchus", "\u{26CE}"),
+    ("orange", "\u{1F34A}"),
+    ("orange_book", "\u{1F4D9}"),
+    ("orange_circle", "\u{1F7E0}"),
+    ("orange_heart", "\u{1F9E1}"),
+    ("orange_square", "\u{1F7E7}"),
+    ("orthodox_cross", "\u{2626}\u{FE0F}"),
+    ("otter", "\u{1F9A6}"),
+    ("outbox_tray", "\u{1F4E4}"),
+    ("owl", "\u{1F989}"),
+    ("ox", "\u{1F402}"),
+    ("oyster", "\u{1F9AA}"),
+    ("package", "\u{1F4E6}"),
+    ("page_facing_up", "\u{1F4C4}"),
+    ("page_with_curl", "\u{1F4C3}"),
+    ("pager", "\u{1F4DF}"),
+    ("paintbrush", "\u{1F58C}\u{FE0F}"),
+    ("palm_tree", "\u{1F334}"),
+    ("palms_up_together", "\u{1F932}"),
+    ("pancakes", "\u{1F95E}"),
+    ("panda_face", "\u{1F43C}"),
+    ("paperclip", "\u{1F4CE}"),
+    ("paperclips", "\u{1F587}\u{FE0F}"),
+    ("parachute", "\u{1FA82}"),
+    ("parking", "\u{1F17F}\u{FE0F}"),
+    ("parrot", "\u{1F99C}"),
+    ("part_alternation_mark", "\u{303D}\u{FE0F}"),
+    ("partly_sunny", "\u{26C5}"),
+    ("partying_face", "\u{1F973}"),
+    ("pause_button", "\u{23F8}\u{FE0F}"),
+    ("paw_prints", "\u{1F43E}"),
+    ("peace_symbol", "\u{262E}\u{FE0F}"),
+    ("peach", "\u{1F351}"),
+    ("peacock", "\u{1F99A}"),
+    ("peanuts", "\u{1F95C}"),
+    ("pear", "\u{1F350}"),
+    ("pen", "\u{1F58A}\u{FE0F}"),
+    ("pencil", "\u{1F4DD}"),
+    ("pencil2", "\u{270F}\u{FE0F}"),
+    ("penguin", "\u{1F427}"),
+    ("pensive", "\u{1F614}"),
+    ("performing_arts", "\u{1F3AD}"),
+    ("persevere", "\u{1F623}"),
+    ("person_frowning", "\u{1F64D}"),
+    ("person_with_pouting_face", "\u{1F64E}"),
+    ("petri_dish", "\u{1F9EB}"),
+    ("phone", "\u{260E}\u{FE0F}"),
+    ("pick", "\u{26CF}\u{FE0F}"),
+    ("pie", "\u{1F967}"),
+    ("pig", "\u{1F437}"),
+    ("pig2", "\u{1F416}"),
+    ("pig_nose", "\u{1F43D}"),
+    ("pill", "\u{1F48A}"),
+    ("pinching_hand", "\u{1F90F}"),
+    ("pineapple", "\u{1F34D}"),
+    ("ping_pong", "\u{1F3D3}"),
+    ("pisces", "\u{2653}"),
+    ("pizza", "\u{1F355}"),
+    ("place_of_worship", "\u{1F6D0}"),
+    ("pleading_face", "\u{1F97A}"),
+    ("point_down", "\u{1F447}"),
+    ("point_left", "\u{1F448}"),
+    ("point_right", "\u{1F449}"),
+    ("point_up", "\u{261D}\u{FE0F}"),
+    ("point_up_2", "\u{1F446}"),
+    ("police_car", "\u{1F693}"),
+    ("poodle", "\u{1F429}"),
+    ("poop", "\u{1F4A9}"),
+    ("popcorn", "\u{1F37F}"),
+    ("postal_horn", "\u{1F4EF}"),
+    ("postbox", "\u{1F4EE}"),
+    ("potable_water", "\u{1F6B0}"),
+    ("potato", "\u{1F954}"),
+    ("pouch", "\u{1F45D}"),
+    ("poultry_leg", "\u{1F357}"),
+    ("pound", "\u{1F4B7}"),
+    ("pout", "\u{1F621}"),
+    ("pouting_cat", "\u{1F63E}"),
+    ("pray", "\u{1F64F}"),
+    ("pretzel", "\u{1F968}"),
+    ("prince", "\u{1F934}"),
+    ("princess", "\u{1F478}"),
+    ("printer", "\u{1F5A8}\u{FE0F}"),
+    ("punch", "\u{1F44A}"),
+    ("purple_circle", "\u{1F7E3}"),
+    ("purple_heart", "\u{1F49C}"),
+    ("purple_square", "\u{1F7EA}"),
+    ("purse", "\u{1F45B}"),
+    ("pushpin", "\u{1F4CC}"),
+    ("put_litter_in_its_place", "\u{1F6AE}"),
+    ("question", "\u{2753}"),
+    ("rabbit", "\u{1F430}"),
+    ("rabbit2", "\u{1F407}"),
+    ("raccoon", "\u{1F99D}"),
+    ("racehorse", "\u{1F40E}"),
+    ("radio", "\u{1F4FB}"),
+    ("radio_button", "\u{1F518}"),
+    ("radioactive", "\u{2622}\u{FE0F}"),
+    ("rage", "\u{1F621}"),
+    ("rainbow", "\u{1F308}"),
+    ("raised_back_of_hand", "\u{1F91A}"),
+    ("raised_eyebrow", "\u{1F928}"),
+    ("raised_hand", "\u{270B}"),
+    ("raised_hand_with_fingers_splayed", "\u{1F590}\u{FE0F}"),
+    ("raised_hands", "\u{1F64C}"),
+    ("raising_hand", "\u{1F64B}"),
+    ("ram", "\u{1F40F}"),
+    ("ramen", "\u{1F35C}"),
+    ("rat", "\u{1F400}"),
+    ("razor", "\u{1FA92}"),
+    ("receipt", "\u{1F9FE}"),
+    ("record_button", "\u{23FA}\u{FE0F}"),
+    ("recycle", "\u{267B}\u{FE0F}"),
+    ("red_car", "\u{1F697}"),
+    ("red_circle", "\u{1F534}"),
+    ("red_envelope", "\u{1F9E7}"),
+    ("red_square", "\u{1F7E5}"),
+    ("registered", "\u{AE}\u{FE0F}"),
+    ("relaxed", "\u{263A}\u{FE0F}"),
+    ("relieved", "\u{1F60C}"),
+    ("reminder_ribbon", "\u{1F397}\u{FE0F}"),
+    ("repeat", "\u{1F501}"),
+    ("repeat_one", "\u{1F502}"),
+    ("restroom", "\u{1F6BB}"),
+    ("revolving_hearts", "\u{1F49E}"),
+    ("rewind", "\u{23EA}"),
+    ("rhinoceros", "\u{1F98F}"),
+    ("ribbon", "\u{1F380}"),
+    ("rice", "\u{1F35A}"),
+    ("rice_ball", "\u{1F359}"),
+    ("rice_cracker", "\u{1F358}"),
+    ("rice_scene", "\u{1F391}"),
+    ("ring", "\u{1F48D}"),
+    ("robot", "\u{1F916}"),
+    ("rocket", "\u{1F680}"),
+    ("rofl", "\u{1F923}"),
+    ("roll_eyes", "\u{1F644}"),
+    ("roll_of_paper", "\u{1F9FB}"),
+    ("roller_coaster", "\u{1F3A2}"),
+    ("rooster", "\u{1F413}"),
+    ("rose", "\u{1F339}"),
+    ("rosette", "\u{1F3F5}\u{FE0F}"),
+    ("rotating_light", "\u{1F6A8}"),
+    ("round_pushpin", "\u{1F4CD}"),
+    ("rugby_football", "\u{1F3C9}"),
+    ("runner", "\u{1F3C3}"),
+    ("running", "\u{1F3C3}"),
+    ("running_shirt_with_sash", "\u{1F3BD}"),
+    ("safety_pin", "\u{1F9F7}"),
+    ("sagittarius", "\u{2650}"),
+    ("sailboat", "\u{26F5}"),
+    ("sake", "\u{1F376}"),
+    ("salt", "\u{1F9C2}"),
+    ("sandal", "\u{1F461}"),
+    ("sandwich", "\u{1F96A}"),
+    ("santa", "\u{1F385}"),
+    ("satellite", "\u{1F4E1}"),
+    ("satisfied", "\u{1F606}"),
+    ("sauropod", "\u{1F995}"),
+    ("saxophone", "\u{1F3B7}"),
+    ("scarf", "\u{1F9E3}"),
+    ("school", "\u{1F3EB}"),
+    ("school_satchel", "\u{1F392}"),
+    ("scissors", "\u{2702}\u{FE0F}"),
+    ("scorpion", "\u{1F982}"),
+    ("scorpius", "\u{264F}"),
+    ("scream", "\u{1F631}"),
+    ("scream_cat", "\u{1F640}"),
+    ("scroll", "\u{1F4DC}"),
+    ("seat", "\u{1F4BA}"),
+    ("see_no_evil", "\u{1F648}"),
+    ("seedling", "\u{1F331}"),
+    ("selfie", "\u{1F933}"),
+    ("shallow_pan_of_food", "\u{1F958}"),
+    ("shamrock", "\u{2618}\u{FE0F}"),
+    ("shark", "\u{1F988}"),
+    ("shaved_ice", "\u{1F367}"),
+    ("sheep", "\u{1F411}"),
+    ("shell", "\u{1F41A}"),
+    ("shield", "\u{1F6E1}\u{FE0F}"),
+    ("ship", "\u{1F6A2}"),
+    ("shirt", "\u{1F455}"),
+    ("shit", "\u{1F4A9}"),
+    ("shoe", "\u{1F45E}"),
+    ("shopping", "\u{1F6CD}\u{FE0F}"),
+    ("shopping_cart", "\u{1F6D2}"),
+    ("shower", "\u{1F6BF}"),
+    ("shrimp", "\u{1F990}"),
+    ("shrug", "\u{1F937}"),
+    ("shushing_face", "\u{1F92B}"),
+    ("signal_strength", "\u{1F4F6}"),
+    ("six_pointed_star", "\u{1F52F}"),
+    ("skateboard", "\u{1F6F9}"),
+    ("ski", "\u{1F3BF}"),
+    ("skull", "\u{1F480}"),
+    ("skull_and_crossbones", "\u{2620}\u{FE0F}"),
+    ("skunk", "\u{1F9A8}"),
+    ("sled", "\u{1F6F7}"),
+    ("sleeping", "\u{1F634}"),
+    ("sleepy", "\u{1F62A}"),
+    ("slightly_frowning_face", "\u{1F641}"),
+    ("slightly_smiling_face", "\u{1F642}"),
+    ("slot_machine", "\u{1F3B0}"),
+    ("sloth", "\u{1F9A5}"),
+    ("small_blue_diamond", "\u{1F539}"),
+    ("small_orange_diamond", "\u{1F538}"),
+    ("small_red_triangle", "\u{1F53A}"),
+    ("small_red_triangle_down", "\u{1F53B}"),
+    ("smile", "\u{1F604}"),
+    ("smile_cat", "\u{1F638}"),
+    ("smiley", "\u{1F603}"),
+    ("smiley_cat", "\u{1F63A}"),
+    ("smiling_face_with_three_hearts", "\u{1F970}"),
+    ("smiling_imp", "\u{1F608}"),
+    ("smirk", "\u{1F60F}"),
+    ("smirk_cat", "\u{1F63C}"),
+    ("smoking", "\u{1F6AC}"),
+    ("snail", "\u{1F40C}"),
+    ("snake", "\u{1F40D}"),
+    ("sneezing_face", "\u{1F927}"),
+    ("snowflake", "\u{2744}\u{FE0F}"),
+    ("snowman", "\u{26C4}"),
+    ("soap", "\u{1F9FC}"),
+    ("sob", "\u{1F62D}"),
+    ("soccer", "\u{26BD}"),
+    ("socks", "\u{1F9E6}"),
+    ("softball", "\u{1F94E}"),
+    ("soon", "\u{1F51C}"),
+    ("sos", "\u{1F198}"),
+    ("sound", "\u{1F509}"),
+    ("space_invader", "\u{1F47E}"),
+    ("spades", "\u{2660}\u{FE0F}"),
+    ("spaghetti", "\u{1F35D}"),
+    ("sparkle", "\u{2747}\u{FE0F}"),
+    ("sparkler", "\u{1F387}"),
+    ("sparkles", "\u{2728}"),
+    ("sparkling_heart", "\u{1F496}"),
+    ("speak_no_evil", "\u{1F64A}"),
+    ("speaker", "\u{1F508}"),
+    ("speaking_head", "\u{1F5E3}\u{FE0F}"),
+    ("speech_balloon", "\u{1F4AC}"),
+    ("speedboat", "\u{1F6A4}"),
+    ("spider", "\u{1F577}\u{FE0F}"),
+    ("spider_web", "\u{1F578}\u{FE0F}"),
+    ("spiral_calendar", "\u{1F5D3}\u{FE0F}"),
+    ("spiral_notepad", "\u{1F5D2}\u{FE0F}"),
+    ("sponge", "\u{1F9FD}"),
+    ("spoon", "\u{1F944}"),
+    ("squid", "\u{1F991}"),
+    ("star", "\u{2B50}"),
+    ("star2", "\u{1F31F}"),
+    ("star_and_crescent", "\u{262A}\u{FE0F}"),
+    ("star_of_david", "\u{2721}\u{FE0F}"),
+    ("star_struck", "\u{1F929}"),
+    ("stars", "\u{1F320}"),
+    ("station", "\u{1F689}"),
+    ("statue_of_liberty", "\u{1F5FD}"),
+    ("steam_locomotive", "\u{1F682}"),
+    ("stethoscope", "\u{1FA7A}"),
+    ("stew", "\u{1F372}"),
+    ("stop_button", "\u{23F9}\u{FE0F}"),
+    ("stop_sign", "\u{1F6D1}"),
+    ("stopwatch", "\u{23F1}\u{FE0F}"),
+    ("straight_ruler", "\u{1F4CF}"),
+    ("strawberry", "\u{1F353}"),
+    ("stuck_out_tongue", "\u{1F61B}"),
+    ("stuck_out_tongue_closed_eyes", "\u{1F61D}"),
+    ("stuck_out_tongue_winking_eye", "\u{1F61C}"),
+    ("studio_microphone", "\u{1F399}\u{FE0F}"),
+    ("stuffed_flatbread", "\u{1F959}"),
+    ("sunflower", "\u{1F33B}"),
+    ("sunglasses", "\u{1F60E}"),
+    ("sunny", "\u{2600}\u{FE0F}"),
+    ("sunrise", "\u{1F305}"),
+    ("superhero", "\u{1F9B8}"),
+    ("sushi", "\u{1F363}"),
+    ("swan", "\u{1F9A2}"),
+    ("sweat", "\u{1F613}"),
+    ("sweat_drops", "\u{1F4A6}"),
+    ("sweat_smile", "\u{1F605}"),
+    ("sweet_potato", "\u{1F360}"),
+    ("symbols", "\u{1F523}"),
+    ("syringe", "\u{1F489}"),
+    ("t-rex", "\u{1F996}"),
+    ("taco", "\u{1F32E}"),
+    ("tada", "\u{1F389}"),
+    ("takeout_box", "\u{1F961}"),
+    ("tanabata_tree", "\u{1F38B}"),
+    ("tangerine", "\u{1F34A}"),
+    ("taurus", "\u{2649}"),
+    ("taxi", "\u{1F695}"),
+    ("tea", "\u{1F375}"),
+    ("teddy_bear", "\u{1F9F8}"),
+    ("telephone", "\u{260E}\u{FE0F}"),
+    ("telephone_receiver", "\u{1F4DE}"),
+    ("telescope", "\u{1F52D}"),
+    ("tennis", "\u{1F3BE}"),
+    ("tent", "\u{26FA}"),
+    ("test_tube", "\u{1F9EA}"),
+    ("thermometer", "\u{1F321}\u{FE0F}"),
+    ("thinking", "\u{1F914}"),
+    ("thought_balloon", "\u{1F4AD}"),
+    ("thread", "\u{1F9F5}"),
+    ("thumbsdown", "\u{1F44E}"),
+    ("thumbsup", "\u{1F44D}"),
+    ("ticket", "\u{1F3AB}"),
+    ("tickets", "\u{1F39F}\u{FE0F}"),
+    ("tiger", "\u{1F42F}"),
+    ("tiger2", "\u{1F405}"),
+    ("timer_clock", "\u{23F2}\u{FE0F}"),
+    ("tipping_hand_person", "\u{1F481}"),
+    ("tired_face", "\u{1F62B}"),
+    ("tm", "\u{2122}\u{FE0F}"),
+    ("toilet", "\u{1F6BD}"),
+    ("tokyo_tower", "\u{1F5FC}"),
+    ("tomato", "\u{1F345}"),
+    ("tongue", "\u{1F445}"),
+    ("toolbox", "\u{1F9F0}"),
+    ("top", "\u{1F51D}"),
+    ("tophat", "\u{1F3A9}"),
+    ("tornado", "\u{1F32A}\u{FE0F}"),
+    ("trackball", "\u{1F5B2}\u{FE0F}"),
+    ("tractor", "\u{1F69C}"),
+    ("traffic_light", "\u{1F6A5}"),
+    ("train", "\u{1F68B}"),
+    ("train2", "\u{1F686}"),
+    ("tram", "\u{1F68A}"),
+    ("triangular_flag_on_post", "\u{1F6A9}"),
+    ("triangular_ruler", "\u{1F4D0}"),
+    ("trident", "\u{1F531}"),
+    ("triumph", "\u{1F624}"),
+    ("trophy", "\u{1F3C6}"),
+    ("tropical_drink", "\u{1F379}"),
+    ("tropical_fish", "\u{1F420}"),
+    ("truck", "\u{1F69A}"),
+    ("trumpet", "\u{1F3BA}"),
+    ("tshirt", "\u{1F455}"),
+    ("tulip", "\u{1F337}"),
+    ("tumbler_glass", "\u{1F943}"),
+    ("turkey", "\u{1F983}"),
+    ("turtle", "\u{1F422}"),
+    ("tv", "\u{1F4FA}"),
+    ("twisted_rightwards_arrows", "\u{1F500}"),
+    ("two_hearts", "\u{1F495}"),
+    ("umbrella", "\u{2614}"),
+    ("unamused", "\u{1F612}"),
+    ("underage", "\u{1F51E}"),
+    ("unicorn", "\u{1F984}"),
+    ("unlock", "\u{1F513}"),
+    ("up", "\u{1F199}"),
+    ("upside_down_face", "\u{1F643}"),
+    ("v", "\u{270C}\u{FE0F}"),
+    ("vampire", "\u{1F9DB}"),
+    ("vertical_traffic_light", "\u{1F6A6}"),
+    ("vhs", "\u{1F4FC}"),
+    ("vibration_mode", "\u{1F4F3}"),
+    ("video_camera", "\u{1F4F9}"),
+    ("video_game", "\u{1F3AE}"),
+    ("violin", "\u{1F3BB}"),
+    ("virgo", "\u{264D}"),
+    ("volcano", "\u{1F30B}"),
+    ("volleyball", "\u{1F3D0}"),
+    ("vomiting_face", "\u{1F92E}"),
+    ("vs", "\u{1F19A}"),
+    ("vulcan_salute", "\u{1F596}"),
+    ("waffle", "\u{1F9C7}"),
+    ("walking", "\u{1F6B6}"),
+    ("warning", "\u{26A0}\u{FE0F}"),
+    ("wastebasket", "\u{1F5D1}\u{FE0F}"),
+    ("watch", "\u{231A}"),
+    ("water_buffalo", "\u{1F403}"),
+    ("watermelon", "\u{1F349}"),
+    ("wave", "\u{1F44B}"),
+    ("wavy_dash", "\u{3030}\u{FE0F}"),
+    ("wc", "\u{1F6BE}"),
+    ("weary", "\u{1F629}"),
+    ("whale", "\u{1F433}"),
+    ("whale2", "\u{1F40B}"),
+    ("wheel_of_dharma", "\u{2638}\u{FE0F}"),
+    ("wheelchair", "\u{267F}"),
+    ("white_check_mark", "\u{2705}"),
+    ("white_circle", "\u{26AA}"),
+    ("white_flag", "\u{1F3F3}\u{FE0F}"),
+    ("white_flower", "\u{1F4AE}"),
+    ("white_heart", "\u{1F90D}"),
+    ("white_large_square", "\u{2B1C}"),
+    ("white_square_button", "\u{1F533}"),
+    ("wilted_flower", "\u{1F940}"),
+    ("wind_chime", "\u{1F390}"),
+    ("wine_glass", "\u{1F377}"),
+    ("wink", "\u{1F609}"),
+    ("wolf", "\u{1F43A}"),
+    ("woman", "\u{1F469}"),
+    ("womans_clothes", "\u{1F45A}"),
+    ("womans_hat", "\u{1F452}"),
+    ("womens", "\u{1F6BA}"),
+    ("woozy_face", "\u{1F974}"),
+    ("worried", "\u{1F61F}"),
+    ("wrench", "\u{1F527}"),
+    ("writing_hand", "\u{270D}\u{FE0F}"),
+    ("x", "\u{274C}"),
+    ("yarn", "\u{1F9F6}"),
+    ("yawning_face", "\u{1F971}"),
+    ("yellow_circle", "\u{1F7E1}"),
+    ("yellow_heart", "\u{1F49B}"),
+    ("yellow_square", "\u{1F7E8}"),
+    ("yen", "\u{1F4B4}"),
+    ("yin_yang", "\u{262F}\u{FE0F}"),
+    ("yo_yo", "\u{1FA80}"),
+    ("yum", "\u{1F60B}"),
+    ("zany_face", "\u{1F92A}"),
+    ("zap", "\u{26A1}"),
+    ("zebra", "\u{1F993}"),
+    ("zipper_mouth_face", "\u{1F910}"),
+    ("zombie", "\u{1F9DF}"),
+    ("zzz", "\u{1F4A4}"),
+];
@@ -0,0 +1,18 @@
+mod data;
+
+use data::EMOJI;
+
+/// Longest shortcode name in [`EMOJI`].
+pub(crate) const MAX_EMOJI_NAME_LEN: usize = 32;
+
+/// Looks up a built-in shortcode name (without the colons), as in `tada` → 🎉.
+#[inline]
+pub(crate) fn lookup_emoji(name: &str) -> Option<&'static str> {
+    if name.len() > MAX_EMOJI_NAME_LEN {
+        return None;
+    }
+    EMOJI
+        .binary_search_by(|(n, _)| n.cmp(&name))
+        .ok()
+        .map(|i| EMOJI[i].1)
+}
@@ -0,0 +1,58 @@
+/// Metadata block at the very start of a document, recognized with
+/// [`ParseOptions::front_matter`](crate::ParseOptions::front_matter).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrontMatter {
+    pub kind: FrontMatterKind,
+    /// The text between the delimiter lines, line endings included. It is
+    /// not parsed; hand it to a YAML or TOML library.
+    pub raw: String,
+}
+
+/// Which delimiter opened the [`FrontMatter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrontMatterKind {
+    /// `---`, closed by `---` or `...`.
+    Yaml,
+    /// `+++`, closed by `+++`.
+    Toml,
+}
+
+/// Rendered HTML or AST of a document, with its front matter split off.
+/// Returned by [`parse_with_front_matter`](crate::parse_with_front_matter)
+/// and [`parse_to_ast_with_front_matter`](crate::parse_to_ast_with_front_matter).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseOutput<T> {
+    pub front_matter: Option<FrontMatter>,
+    /// Everything after the front matter.
+    pub body: T,
+}
+
+/// Splits the front matter off the start of `input`, returning it with the
+/// byte offset and number of lines of the front matter, closing delimiter
+/// included. Inputs without a closing delimiter have no front matter.
+pub(crate) fn split_front_matter(input: &str) -> Option<(FrontMatter, usize, usize)> {
+    let mut lines = input.split_inclusive('\n');
+    let kind = match lines.next()?.trim_end() {
+        "---" => FrontMatterKind::Yaml,
+        "+++" => FrontMatterKind::Toml,
+        _ => return None,
+    };
+    let raw_start = input.find('\n')? + 1;
+    let mut offset = raw_start;
+    for (n, line) in lines.enumerate() {
+        if matches!(
+            (kind, line.trim_end()),
+            (FrontMatterKind::Yaml, "---" | "...") | (FrontMatterKind::Toml, "+++")
+        ) {
+            let front_matter = FrontMatter {
+                kind,
+                raw: input[raw_start..offset].to_string(),
+            };
+            return Some((front_matter, offset + line.len(), n + 2));
+        }
+        offset += line.len();
+    }
+    None
+}
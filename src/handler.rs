@@ -0,0 +1,191 @@
+/// Per-node overrides for HTML output.
+///
+/// Every method is called with the output buffer just before the default
+/// markup for that node would be written. Return `true` after writing your
+/// own markup to skip the default, or `false` to fall back to it. The
+/// provided implementations all return `false`, so a handler only needs to
+/// implement the nodes it cares about.
+///
+/// Paired methods (`heading_open`/`heading_close`, `link_open`/`link_close`,
+/// `container_open`/`container_close`)
+/// are called independently; a handler that replaces an opening tag with a
+/// different element should replace the closing tag too.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse_with_handler, HtmlHandler, ParseOptions};
+///
+/// struct ExternalLinks;
+///
+/// impl HtmlHandler for ExternalLinks {
+///     fn link_open(&mut self, out: &mut String, dest: &str, _title: Option<&str>) -> bool {
+///         if !dest.starts_with("https://") {
+///             return false;
+///         }
+///         out.push_str("<a rel=\"nofollow\" href=\"");
+///         out.push_str(&dest.replace('&', "&amp;").replace('"', "&quot;"));
+///         out.push_str("\">");
+///         true
+///     }
+/// }
+///
+/// let opts = ParseOptions::default();
+/// let html = parse_with_handler("[a](https://x.dev) [b](/local)", &opts, &mut ExternalLinks);
+/// assert_eq!(
+///     html,
+///     "<p><a rel=\"nofollow\" href=\"https://x.dev\">a</a> <a href=\"/local\">b</a></p>\n"
+/// );
+/// ```
+pub trait HtmlHandler {
+    /// A fenced or indented code block. `info` is the full info string (empty
+    /// for indented code) and `literal` the unescaped contents.
+    fn code_block(&mut self, _out: &mut String, _info: &str, _literal: &str) -> bool {
+        false
+    }
+
+    /// Opening tag of a heading. `raw` is the heading's Markdown source and
+    /// `id` its slug when [`heading_ids`](crate::ParseOptions::heading_ids) is on.
+    fn heading_open(
+        &mut self,
+        _out: &mut String,
+        _level: u8,
+        _raw: &str,
+        _id: Option<&str>,
+    ) -> bool {
+        false
+    }
+
+    /// Closing tag of a heading.
+    fn heading_close(&mut self, _out: &mut String, _level: u8) -> bool {
+        false
+    }
+
+    /// Opening `<a>` of a link. `dest` and `title` have entities and escapes
+    /// resolved but are not HTML-escaped or URL-encoded.
+    fn link_open(&mut self, _out: &mut String, _dest: &str, _title: Option<&str>) -> bool {
+        false
+    }
+
+    /// Closing `</a>` of a link.
+    fn link_close(&mut self, _out: &mut String) -> bool {
+        false
+    }
+
+    /// An image. `alt` is the plain-text description, not HTML-escaped.
+    fn image(&mut self, _out: &mut String, _dest: &str, _title: Option<&str>, _alt: &str) -> bool {
+        false
+    }
+
+    /// Math from [`enable_math`](crate::ParseOptions::enable_math). `display`
+    /// is `true` for `$$` blocks, ```` ```math ```` fences and inline `$$…$$`;
+    /// `literal` is the raw TeX source, not HTML-escaped.
+    fn math(&mut self, _out: &mut String, _literal: &str, _display: bool) -> bool {
+        false
+    }
+
+    /// Opening `<div>` of a `:::` container from
+    /// [`enable_containers`](crate::ParseOptions::enable_containers).
+    /// `attributes` is the rest of the fence line after `name`, not HTML-escaped.
+    fn container_open(&mut self, _out: &mut String, _name: &str, _attributes: &str) -> bool {
+        false
+    }
+
+    /// Closing `</div>` of a container.
+    fn container_close(&mut self, _out: &mut String, _name: &str) -> bool {
+        false
+    }
+
+    /// The checkbox at the start of a task list item.
+    fn task_checkbox(&mut self, _out: &mut String, _checked: bool) -> bool {
+        false
+    }
+}
+
+/// Handler used by [`parse`](crate::parse): no overrides.
+pub(crate) struct DefaultHandler;
+
+impl HtmlHandler for DefaultHandler {}
+
+/// Syntax highlighter for code blocks, set through
+/// [`ParseOptions::code_highlighter`](crate::ParseOptions::code_highlighter).
+///
+/// `lang` is the first word of the info string (empty for indented code and
+/// fences without one), `info` the full info string and `code` the raw block
+/// contents. Return the highlighted, already HTML-escaped contents to place
+/// inside `<pre><code class="language-…">`, or `None` to fall back to plain
+/// escaped text.
+///
+/// Any `Fn(&str, &str, &str) -> Option<String> + Send + Sync` closure
+/// implements this trait.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse, ParseOptions};
+///
+/// let opts = ParseOptions {
+///     code_highlighter: Some(Box::new(|lang: &str, _info: &str, code: &str| {
+///         (lang == "sh").then(|| format!("<span class=\"cmd\">{}</span>", code.trim_end()))
+///     })),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     parse("```sh\nls\n```", &opts),
+///     "<pre><code class=\"language-sh\"><span class=\"cmd\">ls</span></code></pre>\n"
+/// );
+/// ```
+pub trait CodeHighlighter {
+    /// Highlights one code block.
+    fn highlight(&self, lang: &str, info: &str, code: &str) -> Option<String>;
+}
+
+impl<F> CodeHighlighter for F
+where
+    F: Fn(&str, &str, &str) -> Option<String> + Send + Sync,
+{
+    fn highlight(&self, lang: &str, info: &str, code: &str) -> Option<String> {
+        self(lang, info, code)
+    }
+}
+
+/// Custom emoji for [`ParseOptions::custom_emoji`](crate::ParseOptions::custom_emoji).
+///
+/// Called with the name between the colons of a `:shortcode:` that is not in
+/// the built-in table. Return the image URL to render it as
+/// `<img class="emoji" src="…" alt=":name:" />`, or `None` to leave the
+/// shortcode as text.
+///
+/// Any `Fn(&str) -> Option<String> + Send + Sync` closure implements this
+/// trait.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse, ParseOptions};
+///
+/// let opts = ParseOptions {
+///     enable_emoji: true,
+///     custom_emoji: Some(Box::new(|name: &str| {
+///         (name == "shipit").then(|| "/emoji/shipit.png".to_string())
+///     })),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     parse(":tada: :shipit: :nope:", &opts),
+///     "<p>\u{1F389} <img class=\"emoji\" src=\"/emoji/shipit.png\" alt=\":shipit:\" /> :nope:</p>\n"
+/// );
+/// ```
+pub trait CustomEmoji {
+    /// Returns the image URL for one shortcode name.
+    fn url(&self, name: &str) -> Option<String>;
+}
+
+impl<F> CustomEmoji for F
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    fn url(&self, name: &str) -> Option<String> {
+        self(name)
+    }
+}
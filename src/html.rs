@@ -1,3 +1,6 @@
+use crate::sanitize::{OpenTags, sanitize_html_into};
+use crate::{ParseOptions, RawHtmlMode};
+
 #[cfg(test)]
 pub(crate) fn escape_html(input: &str) -> String {
     let mut out = String::with_capacity(input.len());
@@ -46,6 +49,34 @@ pub(crate) fn escape_html_into(out: &mut String, input: &str) {
     }
 }
 
+/// Reverses [`escape_html_into`] for text the inline scanner stored pre-escaped.
+pub(crate) fn unescape_html(input: &str) -> std::borrow::Cow<'_, str> {
+    if memchr::memchr(b'&', input.as_bytes()).is_none() {
+        return std::borrow::Cow::Borrowed(input);
+    }
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let (ch, len) = if rest.starts_with("&amp;") {
+            ('&', 5)
+        } else if rest.starts_with("&lt;") {
+            ('<', 4)
+        } else if rest.starts_with("&gt;") {
+            ('>', 4)
+        } else if rest.starts_with("&quot;") {
+            ('"', 6)
+        } else {
+            ('&', 1)
+        };
+        out.push(ch);
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    std::borrow::Cow::Owned(out)
+}
+
 static HEX_CHARS: &[u8; 16] = b"0123456789ABCDEF";
 
 static URL_HTML_SAFE: [bool; 256] = {
@@ -124,11 +155,161 @@ pub(crate) fn encode_url_escaped_into(out: &mut String, url: &str) {
     }
 }
 
+/// Writes an inline raw HTML fragment according to `opts.raw_html`, applying
+/// the tag filter when enabled.
+#[inline]
+pub(crate) fn push_raw_html(
+    out: &mut String,
+    html: &str,
+    opts: &ParseOptions,
+    open_tags: &mut OpenTags,
+) {
+    match opts.raw_html {
+        RawHtmlMode::PassThrough if opts.tagfilter => push_tagfiltered(out, html),
+        RawHtmlMode::PassThrough => out.push_str(html),
+        RawHtmlMode::Escape => escape_html_into(out, html),
+        RawHtmlMode::Omit => {}
+        RawHtmlMode::Sanitize => sanitize_html_into(out, html, opts, open_tags),
+    }
+}
+
+/// Tags escaped by GFM's "disallowed raw HTML" extension.
+const FILTERED_TAGS: [&str; 9] = [
+    "title",
+    "textarea",
+    "style",
+    "xmp",
+    "iframe",
+    "noembed",
+    "noframes",
+    "script",
+    "plaintext",
+];
+
+/// Writes `html`, replacing the `<` of any opening or closing
+/// [`FILTERED_TAGS`] tag with `&lt;`.
+fn push_tagfiltered(out: &mut String, html: &str) {
+    let bytes = html.as_bytes();
+    let mut last = 0;
+    for (i, _) in html.match_indices('<') {
+        if is_filtered_tag(&bytes[i + 1..]) {
+            out.push_str(&html[last..i]);
+            out.push_str("&lt;");
+            last = i + 1;
+        }
+    }
+    out.push_str(&html[last..]);
+}
+
+fn is_filtered_tag(after_lt: &[u8]) -> bool {
+    let rest = after_lt.strip_prefix(b"/").unwrap_or(after_lt);
+    FILTERED_TAGS.iter().any(|tag| {
+        rest.len() > tag.len()
+            && rest[..tag.len()].eq_ignore_ascii_case(tag.as_bytes())
+            && match rest[tag.len()] {
+                b'>' | b' ' | b'\t' | b'\n' | b'\r' | b'\x0c' => true,
+                b'/' => rest.get(tag.len() + 1) == Some(&b'>'),
+                _ => false,
+            }
+    })
+}
+
+/// Returns `url`, or `""` when `opts.safe_urls` is set and its scheme is not
+/// allowed. URLs without a scheme are always kept; images also accept
+/// `data:image/*`.
+pub(crate) fn filter_url<'u>(url: &'u str, is_image: bool, opts: &ParseOptions) -> &'u str {
+    if !opts.safe_urls || is_allowed_url(url, is_image, &opts.allowed_url_schemes) {
+        url
+    } else {
+        ""
+    }
+}
+
+pub(crate) fn is_allowed_url(url: &str, is_image: bool, allowed: &[String]) -> bool {
+    // Browsers ignore leading control characters and spaces, and tabs or
+    // newlines anywhere in the URL, so `java\tscript:` is still a scheme.
+    let url: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let Some(colon) = url.find(':') else {
+        return true;
+    };
+    let scheme = &url[..colon];
+    let is_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'));
+    if !is_scheme {
+        return true;
+    }
+    if allowed.iter().any(|a| a.eq_ignore_ascii_case(scheme)) {
+        return true;
+    }
+    is_image
+        && scheme.eq_ignore_ascii_case("data")
+        && url[colon + 1..]
+            .get(..6)
+            .is_some_and(|mime| mime.eq_ignore_ascii_case("image/"))
+}
+
 #[inline(always)]
 pub(crate) fn trim_cr(line: &str) -> &str {
     line.strip_suffix('\r').unwrap_or(line)
 }
 
+/// GitHub-style heading slugs: lowercased, punctuation dropped, spaces turned
+/// into `-`, with `-1`, `-2`, … appended to repeats.
+#[derive(Default)]
+pub(crate) struct Slugger {
+    seen: rustc_hash::FxHashMap<String, usize>,
+    /// Explicit ids already given to a heading.
+    claimed: rustc_hash::FxHashSet<String>,
+}
+
+impl Slugger {
+    pub(crate) fn slug(&mut self, text: &str) -> String {
+        let mut base = String::with_capacity(text.len());
+        for c in text.trim().chars() {
+            if c == ' ' {
+                base.push('-');
+            } else if c.is_alphanumeric() || c == '-' || c == '_' {
+                base.extend(c.to_lowercase());
+            }
+        }
+        self.unique(base)
+    }
+
+    /// Keeps generated slugs from taking an explicit `{#id}`, wherever in the
+    /// document it appears.
+    pub(crate) fn reserve(&mut self, id: &str) {
+        self.seen.entry(id.to_string()).or_insert(0);
+    }
+
+    /// Returns the explicit `id` the first time it is claimed, and a `-1`,
+    /// `-2`, … suffixed copy for repeats.
+    pub(crate) fn claim(&mut self, id: &str) -> String {
+        self.reserve(id);
+        if self.claimed.insert(id.to_string()) {
+            id.to_string()
+        } else {
+            self.unique(id.to_string())
+        }
+    }
+
+    fn unique(&mut self, base: String) -> String {
+        let mut slug = base.clone();
+        while self.seen.contains_key(&slug) {
+            let count = self.seen.get_mut(&base).unwrap();
+            *count += 1;
+            slug = format!("{base}-{count}");
+        }
+        self.seen.insert(slug.clone(), 0);
+        slug
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,8 +337,80 @@ mod tests {
         assert_eq!(escape_html("hello world"), "hello world");
     }
 
+    #[test]
+    fn unescape_roundtrip() {
+        let raw = "a < b & \"c\" > d &copy;";
+        assert_eq!(unescape_html(&escape_html(raw)), raw);
+    }
+
     #[test]
     fn mixed_content() {
         assert_eq!(escape_html("a < b & c > d"), "a &lt; b &amp; c &gt; d");
     }
+
+    #[test]
+    fn slugs_match_github() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Hello, World!"), "hello-world");
+        assert_eq!(slugger.slug("Hello World"), "hello-world-1");
+        assert_eq!(slugger.slug("hello world"), "hello-world-2");
+        assert_eq!(slugger.slug("hello-world-1"), "hello-world-1-1");
+        assert_eq!(slugger.slug("a"), "a");
+        assert_eq!(slugger.slug("a-1"), "a-1");
+        assert_eq!(slugger.slug("a"), "a-2");
+        assert_eq!(slugger.slug("Übersicht & API_v2"), "übersicht--api_v2");
+    }
+
+    #[test]
+    fn filters_unsafe_url_schemes() {
+        let opts = ParseOptions {
+            safe_urls: true,
+            ..Default::default()
+        };
+        for url in [
+            "https://a.b",
+            "MAILTO:x@y.z",
+            "/path",
+            "#frag",
+            "?q=a:b",
+            "a/b:c",
+            "1:x",
+        ] {
+            assert_eq!(filter_url(url, false, &opts), url);
+        }
+        for url in [
+            "javascript:alert(1)",
+            " JaVa\tScRiPt:x",
+            "vbscript:x",
+            "data:text/html,x",
+        ] {
+            assert_eq!(filter_url(url, false, &opts), "");
+        }
+        assert_eq!(filter_url("data:image/png;base64,AA", false, &opts), "");
+        assert_eq!(
+            filter_url("data:image/png;base64,AA", true, &opts),
+            "data:image/png;base64,AA"
+        );
+        assert_eq!(filter_url("data:text/html,x", true, &opts), "");
+        assert_eq!(
+            filter_url("javascript:x", false, &ParseOptions::default()),
+            "javascript:x"
+        );
+    }
+
+    #[test]
+    fn tagfilter_escapes_disallowed_tags() {
+        let mut out = String::new();
+        push_tagfiltered(
+            &mut out,
+            "<strong> <title> <style> <em> <blockquote> <xmp> is disallowed. <XMP> is also disallowed. </blockquote>",
+        );
+        assert_eq!(
+            out,
+            "<strong> &lt;title> &lt;style> <em> <blockquote> &lt;xmp> is disallowed. &lt;XMP> is also disallowed. </blockquote>"
+        );
+        let mut out = String::new();
+        push_tagfiltered(&mut out, "<script/></SCRIPT\n><scripts> <iframe");
+        assert_eq!(out, "&lt;script/>&lt;/SCRIPT\n><scripts> <iframe");
+    }
 }
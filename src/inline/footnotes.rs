@@ -0,0 +1,149 @@
+use super::normalize_reference_label;
+use crate::ast::Block;
+use crate::html::encode_url_escaped_into;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+
+/// Footnote definitions known to the inline scanner and the numbers handed
+/// out to them, in order of first reference.
+#[derive(Default)]
+pub(crate) struct Footnotes {
+    /// Normalized label → label as written in the definition, used for ids.
+    defined: FxHashMap<String, String>,
+    /// Normalized labels in order of first reference.
+    order: Vec<String>,
+    /// Normalized label → (1-based number, references so far).
+    counts: FxHashMap<String, (usize, usize)>,
+}
+
+impl Footnotes {
+    /// Registers every footnote definition in `root` and returns their
+    /// contents by normalized label. The first definition of a label wins.
+    pub(crate) fn collect<'b>(&mut self, root: &'b Block) -> FxHashMap<String, &'b [Block]> {
+        let mut defs = FxHashMap::default();
+        let mut stack: Vec<&Block> = vec![root];
+        while let Some(block) = stack.pop() {
+            match block {
+                Block::Document { children, .. }
+                | Block::BlockQuote { children, .. }
+                | Block::Alert { children, .. }
+                | Block::Container { children, .. }
+                | Block::DefinitionList { children, .. }
+                | Block::DefinitionDetails { children, .. }
+                | Block::List { children, .. }
+                | Block::ListItem { children, .. } => stack.extend(children.iter().rev()),
+                Block::FootnoteDefinition {
+                    label, children, ..
+                } => {
+                    let key = normalize_reference_label(label).into_owned();
+                    if let Entry::Vacant(entry) = defs.entry(key) {
+                        self.defined.insert(entry.key().clone(), label.clone());
+                        entry.insert(children.as_slice());
+                    }
+                    stack.extend(children.iter().rev());
+                }
+                _ => {}
+            }
+        }
+        defs
+    }
+
+    #[inline]
+    pub(crate) fn is_defined(&self, label: &str) -> bool {
+        !self.defined.is_empty()
+            && self
+                .defined
+                .contains_key(&*normalize_reference_label(label))
+    }
+
+    /// Normalized labels of the referenced footnotes, in order of first reference.
+    pub(crate) fn referenced(&self) -> &[String] {
+        &self.order
+    }
+
+    /// Writes the superscript link for a reference to `label` and numbers it
+    /// on first use. Returns `false`, writing nothing, if `label` is not defined.
+    pub(crate) fn push_reference(&mut self, out: &mut String, label: &str) -> bool {
+        let key = normalize_reference_label(label);
+        let Some(id) = self.defined.get(&*key) else {
+            return false;
+        };
+        let (number, count) = match self.counts.get_mut(&*key) {
+            Some(entry) => {
+                entry.1 += 1;
+                *entry
+            }
+            None => {
+                self.order.push(key.to_string());
+                let entry = (self.order.len(), 1);
+                self.counts.insert(key.into_owned(), entry);
+                entry
+            }
+        };
+        out.push_str("<sup class=\"footnote-ref\"><a href=\"#fn-");
+        encode_url_escaped_into(out, id);
+        out.push_str("\" id=\"fnref-");
+        encode_url_escaped_into(out, id);
+        if count > 1 {
+            out.push('-');
+            out.push_str(&count.to_string());
+        }
+        out.push_str("\" data-footnote-ref>");
+        out.push_str(&number.to_string());
+        out.push_str("</a></sup>");
+        true
+    }
+
+    /// Opens the `<li>` of the footnote at `idx` in [`referenced`](Self::referenced).
+    pub(crate) fn push_item_open(&self, out: &mut String, idx: usize) {
+        out.push_str("<li id=\"fn-");
+        encode_url_escaped_into(out, &self.defined[&self.order[idx]]);
+        out.push_str("\">\n");
+    }
+
+    /// Writes one back link per reference to the footnote at `idx`.
+    pub(crate) fn push_backrefs(&self, out: &mut String, idx: usize) {
+        let key = &self.order[idx];
+        let id = &self.defined[key];
+        let (number, count) = self.counts[key];
+        for n in 1..=count {
+            let suffix = if n > 1 {
+                format!("{number}-{n}")
+            } else {
+                number.to_string()
+            };
+            if n > 1 {
+                out.push(' ');
+            }
+            out.push_str("<a href=\"#fnref-");
+            encode_url_escaped_into(out, id);
+            if n > 1 {
+                out.push('-');
+                out.push_str(&n.to_string());
+            }
+            out.push_str(
+                "\" class=\"footnote-backref\" data-footnote-backref data-footnote-backref-idx=\"",
+            );
+            out.push_str(&suffix);
+            out.push_str("\" aria-label=\"Back to reference ");
+            out.push_str(&suffix);
+            out.push_str("\">↩");
+            if n > 1 {
+                out.push_str("<sup class=\"footnote-ref\">");
+                out.push_str(&n.to_string());
+                out.push_str("</sup>");
+            }
+            out.push_str("</a>");
+        }
+    }
+}
+
+/// Whether `label` (between `[^` and `]`) can name a footnote.
+#[inline]
+pub(crate) fn is_footnote_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 999
+        && !label
+            .bytes()
+            .any(|b| b.is_ascii_whitespace() || matches!(b, b'[' | b']'))
+}
@@ -1,10 +1,20 @@
+mod footnotes;
 mod links;
 mod render;
 mod scanner;
+mod tree;
+
+pub(crate) use footnotes::{Footnotes, is_footnote_label};
+pub(crate) use render::{inline_plain_text, render_inlines};
 
 use crate::ParseOptions;
+use crate::ast::{Attributes, Inline};
+use crate::attributes::{parse_attributes, push_attributes};
+use crate::emoji::lookup_emoji;
 use crate::entities;
+use crate::handler::HtmlHandler;
 use crate::html::escape_html_into;
+use crate::sanitize::OpenTags;
 use rustc_hash::FxHashMap;
 use std::borrow::Cow;
 use std::rc::Rc;
@@ -115,6 +125,19 @@ static SPECIAL: [u8; 256] = {
     t[b'+' as usize] = both;
     t[b':' as usize] = both;
     t[b'@' as usize] = both;
+    t[b'$' as usize] = both;
+    t[b'^' as usize] = both;
+    t
+};
+
+/// [`SPECIAL`] plus the characters rewritten by `smart_punctuation`.
+static SPECIAL_SMART: [u8; 256] = {
+    let mut t = SPECIAL;
+    let both = SPECIAL_ANY | SPECIAL_COMPLEX;
+    t[b'\'' as usize] = both;
+    t[b'"' as usize] = both;
+    t[b'-' as usize] = both;
+    t[b'.' as usize] = both;
     t
 };
 
@@ -125,6 +148,7 @@ pub(crate) fn parse_inline_pass(
     refs: &LinkRefMap,
     opts: &ParseOptions,
     bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
 ) {
     let bytes = raw.as_bytes();
 
@@ -138,12 +162,20 @@ pub(crate) fn parse_inline_pass(
         match b {
             b'*' | b'_' => has_emphasis = true,
             b'\\' | b'\n' => has_breaks = true,
+            b'\'' | b'"' | b'-' | b'.' if opts.smart_punctuation => {
+                requires_full_inline = true;
+                break;
+            }
             b'>' | b'"' => needs_html_escape = true,
             b'`' | b'!' | b'[' | b']' | b'<' | b'&' => {
                 requires_full_inline = true;
                 break;
             }
-            b'~' if opts.enable_strikethrough => {
+            b'~' if opts.enable_strikethrough || opts.enable_subscript => {
+                requires_full_inline = true;
+                break;
+            }
+            b'^' if opts.enable_superscript => {
                 requires_full_inline = true;
                 break;
             }
@@ -159,15 +191,26 @@ pub(crate) fn parse_inline_pass(
                 requires_full_inline = true;
                 break;
             }
+            b':' if opts.enable_emoji => {
+                requires_full_inline = true;
+                break;
+            }
+            b'$' if opts.enable_math => {
+                requires_full_inline = true;
+                break;
+            }
             _ => {}
         }
     }
 
     if !requires_full_inline {
+        let max_delims = opts.max_inline_delimiters.unwrap_or(usize::MAX);
         if has_breaks {
-            emit_breaks_and_emphasis(out, raw, bytes, opts, &mut bufs.em_delims);
+            bufs.delimiter_limit_hit |=
+                emit_breaks_and_emphasis(out, raw, bytes, opts, &mut bufs.em_delims, max_delims);
         } else if has_emphasis {
-            emit_emphasis_only(out, raw, bytes, &mut bufs.em_delims);
+            bufs.delimiter_limit_hit |=
+                emit_emphasis_only(out, raw, bytes, &mut bufs.em_delims, max_delims);
         } else if needs_html_escape {
             escape_html_into(out, raw);
         } else {
@@ -185,7 +228,25 @@ pub(crate) fn parse_inline_pass(
     if !p.delims.is_empty() {
         p.process_emphasis(0);
     }
-    p.render_to_html(out, opts);
+    p.render_to_html(out, opts, h);
+}
+
+/// Runs the full inline scanner over `raw` and returns the resulting inline tree.
+pub(crate) fn parse_inlines(
+    raw: &str,
+    refs: &LinkRefMap,
+    opts: &ParseOptions,
+    bufs: &mut InlineBuffers,
+) -> Vec<Inline> {
+    if raw.is_empty() {
+        return Vec::new();
+    }
+    let mut p = InlineScanner::new_with_bufs(raw, refs, opts, bufs);
+    p.scan_all();
+    if !p.delims.is_empty() {
+        p.process_emphasis(0);
+    }
+    p.build_inlines()
 }
 
 #[derive(Clone, Copy)]
@@ -272,6 +333,18 @@ fn process_em_delims(delims: &mut [EmDelim]) {
     }
 }
 
+/// Writes what is left of a delimiter run after emphasis matching. Quotes
+/// are only delimiters with `smart_punctuation`: an unmatched `'` is an
+/// apostrophe, and an unmatched `"` closes if it can, else opens.
+#[inline]
+fn push_delim_text(out: &mut String, kind: u8, count: u16, can_close: bool) {
+    match kind {
+        b'\'' => out.push('’'),
+        b'"' => out.push(if can_close { '”' } else { '“' }),
+        _ => out.extend(std::iter::repeat_n(kind as char, count as usize)),
+    }
+}
+
 #[inline(always)]
 fn render_em_delim(out: &mut String, d: &EmDelim) {
     for j in 0..d.close_em_len as usize {
@@ -296,7 +369,15 @@ fn render_em_delim(out: &mut String, d: &EmDelim) {
     }
 }
 
-fn scan_em_delims(raw: &str, bytes: &[u8], skip_escapes: bool, buf: &mut Vec<EmDelim>) {
+/// Collects emphasis delimiter runs into `buf`, stopping after `max` runs.
+/// Returns whether the limit cut the scan short.
+fn scan_em_delims(
+    raw: &str,
+    bytes: &[u8],
+    skip_escapes: bool,
+    buf: &mut Vec<EmDelim>,
+    max: usize,
+) -> bool {
     buf.clear();
     let len = bytes.len();
     let mut i = 0;
@@ -319,6 +400,9 @@ fn scan_em_delims(raw: &str, bytes: &[u8], skip_escapes: bool, buf: &mut Vec<EmD
             }
             continue;
         }
+        if buf.len() >= max {
+            return true;
+        }
         let run_start = i;
         i += 1;
         while i < len && bytes[i] == b {
@@ -346,13 +430,21 @@ fn scan_em_delims(raw: &str, bytes: &[u8], skip_escapes: bool, buf: &mut Vec<EmD
             close_em_len: 0,
         });
     }
+    false
 }
 
-fn emit_emphasis_only(out: &mut String, raw: &str, bytes: &[u8], em_buf: &mut Vec<EmDelim>) {
-    scan_em_delims(raw, bytes, false, em_buf);
+/// Returns whether the delimiter limit was hit.
+fn emit_emphasis_only(
+    out: &mut String,
+    raw: &str,
+    bytes: &[u8],
+    em_buf: &mut Vec<EmDelim>,
+    max_delims: usize,
+) -> bool {
+    let limited = scan_em_delims(raw, bytes, false, em_buf, max_delims);
     if em_buf.is_empty() {
         escape_html_into(out, raw);
-        return;
+        return limited;
     }
     process_em_delims(em_buf);
 
@@ -367,16 +459,19 @@ fn emit_emphasis_only(out: &mut String, raw: &str, bytes: &[u8], em_buf: &mut Ve
     if text_pos < bytes.len() {
         escape_html_into(out, &raw[text_pos..]);
     }
+    limited
 }
 
+/// Returns whether the delimiter limit was hit.
 fn emit_breaks_and_emphasis(
     out: &mut String,
     raw: &str,
     bytes: &[u8],
     opts: &ParseOptions,
     em_buf: &mut Vec<EmDelim>,
-) {
-    scan_em_delims(raw, bytes, true, em_buf);
+    max_delims: usize,
+) -> bool {
+    let limited = scan_em_delims(raw, bytes, true, em_buf, max_delims);
     if !em_buf.is_empty() {
         process_em_delims(em_buf);
     }
@@ -399,6 +494,7 @@ fn emit_breaks_and_emphasis(
             di += 1;
         }
     }
+    limited
 }
 
 #[inline]
@@ -482,6 +578,12 @@ pub(crate) struct InlineBuffers {
     brackets: Vec<BracketInfo>,
     links: Vec<LinkInfo>,
     em_delims: Vec<EmDelim>,
+    /// Set once any paragraph hit `max_inline_delimiters`.
+    pub(crate) delimiter_limit_hit: bool,
+    /// Footnote definitions and numbering for the whole document.
+    pub(crate) footnotes: Footnotes,
+    /// Elements left open by sanitized raw HTML.
+    pub(crate) open_tags: OpenTags,
 }
 
 impl InlineBuffers {
@@ -492,6 +594,9 @@ impl InlineBuffers {
             brackets: Vec::new(),
             links: Vec::new(),
             em_delims: Vec::new(),
+            delimiter_limit_hit: false,
+            footnotes: Footnotes::default(),
+            open_tags: OpenTags::default(),
         }
     }
 }
@@ -534,6 +639,8 @@ struct LinkInfo {
     dest: LinkDest,
     title: Option<Rc<str>>,
     is_image: bool,
+    /// A `{…}` block right after an image.
+    attrs: Option<Box<Attributes>>,
 }
 
 #[derive(Clone, Debug)]
@@ -563,6 +670,14 @@ enum InlineItem {
     BracketOpen {
         is_image: bool,
     },
+    /// `[^label]` reference to a defined footnote; byte range of the label.
+    FootnoteRef(u32, u32),
+    /// Math as a byte range of its content, and whether it is display math.
+    Math(u32, u32, bool),
+    /// `:name:` shortcode: byte range of the name and the Unicode emoji.
+    Emoji(u32, u32, &'static str),
+    /// `:name:` shortcode from `custom_emoji`: byte range of the name and the image URL.
+    CustomEmoji(u32, u32, String),
     LinkStart(u16),
     LinkEnd,
 }
@@ -588,6 +703,14 @@ struct InlineScanner<'a> {
     links: &'a mut Vec<LinkInfo>,
     /// Bitfield: bit N set means no closing backtick run of length N exists.
     backtick_no_match: u64,
+    /// No closing `$` / `$$` exists at or after these offsets.
+    math_no_close: usize,
+    display_math_no_close: usize,
+    /// `max_inline_delimiters`, applied to `delims` and `brackets` each.
+    max_delims: usize,
+    limit_hit: &'a mut bool,
+    footnotes: &'a mut Footnotes,
+    open_tags: &'a mut OpenTags,
 }
 
 impl<'a> InlineScanner<'a> {
@@ -612,6 +735,12 @@ impl<'a> InlineScanner<'a> {
             brackets: &mut bufs.brackets,
             links: &mut bufs.links,
             backtick_no_match: 0,
+            math_no_close: usize::MAX,
+            display_math_no_close: usize::MAX,
+            max_delims: opts.max_inline_delimiters.unwrap_or(usize::MAX),
+            limit_hit: &mut bufs.delimiter_limit_hit,
+            footnotes: &mut bufs.footnotes,
+            open_tags: &mut bufs.open_tags,
         }
     }
 }
@@ -671,6 +800,12 @@ fn flanking(marker: u8, before: char, after: char) -> (bool, bool) {
             left_flanking && (!right_flanking || is_punctuation_char(before)),
             right_flanking && (!left_flanking || is_punctuation_char(after)),
         )
+    } else if matches!(marker, b'\'' | b'"') {
+        // Smart quotes, as in cmark: `don't` and `5'10"` only close.
+        (
+            left_flanking && !right_flanking && !matches!(before, ']' | ')'),
+            right_flanking,
+        )
     } else {
         (left_flanking, right_flanking)
     }
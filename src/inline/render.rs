@@ -1,11 +1,31 @@
+use super::tree::link_dest_str;
 use super::*;
 use crate::ParseOptions;
+use crate::html::{encode_url_escaped_into, filter_url, push_raw_html, unescape_html};
 
-static EM_CLOSE: [&str; 6] = ["</em>", "</em>", "</strong>", "</del>", "</mark>", "</u>"];
-static EM_OPEN: [&str; 6] = ["<em>", "<em>", "<strong>", "<del>", "<mark>", "<u>"];
+static EM_CLOSE: [&str; 8] = [
+    "</em>",
+    "</em>",
+    "</strong>",
+    "</del>",
+    "</mark>",
+    "</u>",
+    "</sup>",
+    "</sub>",
+];
+static EM_OPEN: [&str; 8] = [
+    "<em>", "<em>", "<strong>", "<del>", "<mark>", "<u>", "<sup>", "<sub>",
+];
+/// Written from the image itself; `{src=…}` cannot bypass `safe_urls`.
+const IMAGE_ATTRIBUTES: &[&str] = &["src", "alt", "title"];
 
 impl<'a> InlineScanner<'a> {
-    pub(super) fn render_to_html(&self, out: &mut String, opts: &ParseOptions) {
+    pub(super) fn render_to_html(
+        &mut self,
+        out: &mut String,
+        opts: &ParseOptions,
+        h: &mut dyn HtmlHandler,
+    ) {
         let mut tag_buf: [u8; 16] = [0; 16];
         let mut tag_len: usize = 0;
         let mut i = 0;
@@ -22,15 +42,17 @@ impl<'a> InlineScanner<'a> {
                     out.push_str(unsafe { std::str::from_utf8_unchecked(&buf[..*len as usize]) });
                 }
                 InlineItem::RawHtml(start, end) => {
-                    out.push_str(&self.input[*start..*end]);
+                    push_raw_html(out, &self.input[*start..*end], opts, self.open_tags);
                 }
                 InlineItem::Autolink(start, end, is_email) => {
                     let content = &self.input[*start as usize..*end as usize];
                     out.push_str("<a href=\"");
                     if *is_email {
                         out.push_str("mailto:");
+                        encode_url_escaped_into(out, content);
+                    } else {
+                        encode_url_escaped_into(out, filter_url(content, false, opts));
                     }
-                    crate::html::encode_url_escaped_into(out, content);
                     out.push_str("\">");
                     escape_html_into(out, content);
                     out.push_str("</a>");
@@ -56,6 +78,7 @@ impl<'a> InlineScanner<'a> {
                 InlineItem::DelimRun {
                     kind,
                     count,
+                    can_close,
                     open_em,
                     close_em,
                     ..
@@ -67,9 +90,7 @@ impl<'a> InlineScanner<'a> {
                         }
                     }
                     if *count > 0 {
-                        for _ in 0..*count {
-                            out.push(*kind as char);
-                        }
+                        push_delim_text(out, *kind, *count, *can_close);
                     }
                     for &size in open_em.as_slice().iter().rev() {
                         if tag_len < 16 {
@@ -86,11 +107,28 @@ impl<'a> InlineScanner<'a> {
                         out.push('[');
                     }
                 }
+                InlineItem::Math(start, end, display) => {
+                    push_math(
+                        out,
+                        &self.input[*start as usize..*end as usize],
+                        *display,
+                        h,
+                    );
+                }
+                InlineItem::FootnoteRef(start, end) => {
+                    let label = &self.input[*start as usize..*end as usize];
+                    self.footnotes.push_reference(out, label);
+                }
+                InlineItem::Emoji(_, _, literal) => out.push_str(literal),
+                InlineItem::CustomEmoji(start, end, url) => {
+                    push_custom_emoji(out, &self.input[*start as usize..*end as usize], url, opts);
+                }
                 InlineItem::LinkStart(link_idx) => {
                     let LinkInfo {
                         dest,
                         title,
                         is_image,
+                        attrs,
                     } = &self.links[*link_idx as usize];
                     if *is_image {
                         let alt_start = i + 1;
@@ -110,28 +148,39 @@ impl<'a> InlineScanner<'a> {
                             alt_end += 1;
                         }
                         let alt = self.collect_alt_text(alt_start, alt_end);
+                        i = alt_end;
+                        let dest = filter_url(link_dest_str(dest, self.input), true, opts);
+                        if h.image(out, dest, title.as_deref(), &alt) {
+                            i += 1;
+                            continue;
+                        }
                         out.push_str("<img src=\"");
-                        write_link_dest(out, dest, self.input);
+                        encode_url_escaped_into(out, dest);
                         out.push_str("\" alt=\"");
-                        out.push_str(&alt);
+                        escape_html_into(out, &alt);
                         out.push('"');
                         if let Some(t) = title {
                             out.push_str(" title=\"");
                             escape_html_into(out, t);
                             out.push('"');
                         }
+                        if let Some(attrs) = attrs {
+                            push_attributes(out, attrs, IMAGE_ATTRIBUTES, true, opts);
+                        }
                         out.push_str(" />");
-                        i = alt_end;
                     } else {
-                        out.push_str("<a href=\"");
-                        write_link_dest(out, dest, self.input);
-                        out.push('"');
-                        if let Some(t) = title {
-                            out.push_str(" title=\"");
-                            escape_html_into(out, t);
+                        let dest = filter_url(link_dest_str(dest, self.input), false, opts);
+                        if !h.link_open(out, dest, title.as_deref()) {
+                            out.push_str("<a href=\"");
+                            encode_url_escaped_into(out, dest);
                             out.push('"');
+                            if let Some(t) = title {
+                                out.push_str(" title=\"");
+                                escape_html_into(out, t);
+                                out.push('"');
+                            }
+                            out.push('>');
                         }
-                        out.push('>');
                         if tag_len < 16 {
                             tag_buf[tag_len] = 0;
                             tag_len += 1;
@@ -141,7 +190,9 @@ impl<'a> InlineScanner<'a> {
                 InlineItem::LinkEnd => {
                     if tag_len > 0 && tag_buf[tag_len - 1] == 0 {
                         tag_len -= 1;
-                        out.push_str("</a>");
+                        if !h.link_close(out) {
+                            out.push_str("</a>");
+                        }
                     }
                 }
             }
@@ -149,23 +200,35 @@ impl<'a> InlineScanner<'a> {
         }
     }
 
+    /// Plain (unescaped) text of the items in an image description, for its
+    /// `alt` attribute.
     pub(super) fn collect_alt_text(&self, start: usize, end: usize) -> String {
         let mut s = String::new();
         for idx in start..end {
             match &self.items[idx] {
                 InlineItem::TextRange(a, b) => s.push_str(&self.input[*a..*b]),
-                InlineItem::CodeRange(a, b) => s.push_str(&self.input[*a as usize..*b as usize]),
-                InlineItem::TextOwned(t) | InlineItem::Code(t) => s.push_str(t),
-                InlineItem::TextStatic(t) => s.push_str(t),
+                InlineItem::CodeRange(a, b) | InlineItem::Math(a, b, _) => {
+                    s.push_str(&self.input[*a as usize..*b as usize])
+                }
+                InlineItem::Code(t) => s.push_str(t),
+                InlineItem::TextOwned(t) => s.push_str(&unescape_html(t)),
+                InlineItem::TextStatic(t) => s.push_str(&unescape_html(t)),
+                InlineItem::Emoji(_, _, t) => s.push_str(t),
                 InlineItem::TextInline { buf, len } => {
                     // SAFETY: `buf` is constructed from UTF-8 bytes and `len` tracks initialized prefix length.
                     s.push_str(unsafe { std::str::from_utf8_unchecked(&buf[..*len as usize]) });
                 }
-                InlineItem::DelimRun { kind, count, .. } => {
-                    for _ in 0..*count {
-                        s.push(*kind as char);
-                    }
+                InlineItem::CustomEmoji(a, b, _) => {
+                    s.push(':');
+                    s.push_str(&self.input[*a as usize..*b as usize]);
+                    s.push(':');
                 }
+                InlineItem::DelimRun {
+                    kind,
+                    count,
+                    can_close,
+                    ..
+                } if *count > 0 => push_delim_text(&mut s, *kind, *count, *can_close),
                 InlineItem::BracketOpen { is_image: true, .. } => s.push_str("!["),
                 InlineItem::BracketOpen { .. } => s.push('['),
                 _ => {}
@@ -175,18 +238,210 @@ impl<'a> InlineScanner<'a> {
     }
 }
 
-#[inline]
-pub(super) fn write_link_dest(out: &mut String, dest: &LinkDest, input: &str) {
-    match dest {
-        LinkDest::Range(s, e) => {
-            let s = *s as usize;
-            let e = *e as usize;
-            if s < e {
-                crate::html::encode_url_escaped_into(out, &input[s..e]);
+fn push_math(out: &mut String, literal: &str, display: bool, h: &mut dyn HtmlHandler) {
+    if h.math(out, literal, display) {
+        return;
+    }
+    out.push_str(if display {
+        "<span class=\"math display\">"
+    } else {
+        "<span class=\"math inline\">"
+    });
+    escape_html_into(out, literal);
+    out.push_str("</span>");
+}
+
+fn push_custom_emoji(out: &mut String, name: &str, url: &str, opts: &ParseOptions) {
+    out.push_str("<img class=\"emoji\" src=\"");
+    encode_url_escaped_into(out, filter_url(url, true, opts));
+    out.push_str("\" alt=\":");
+    escape_html_into(out, name);
+    out.push_str(":\" />");
+}
+
+enum InlineWork<'i> {
+    Node(&'i Inline),
+    Close(&'static str),
+    CloseLink,
+}
+
+/// Renders an already-built inline tree (see [`Inline`]) to HTML, producing the
+/// same markup `render_to_html` emits for the equivalent scanned items.
+pub(crate) fn render_inlines(
+    out: &mut String,
+    inlines: &[Inline],
+    opts: &ParseOptions,
+    footnotes: &mut Footnotes,
+    open_tags: &mut OpenTags,
+    h: &mut dyn HtmlHandler,
+) {
+    let mut stack: Vec<InlineWork<'_>> = Vec::with_capacity(16);
+    stack.extend(inlines.iter().rev().map(InlineWork::Node));
+
+    while let Some(work) = stack.pop() {
+        let node = match work {
+            InlineWork::Close(tag) => {
+                out.push_str(tag);
+                continue;
+            }
+            InlineWork::CloseLink => {
+                if !h.link_close(out) {
+                    out.push_str("</a>");
+                }
+                continue;
+            }
+            InlineWork::Node(node) => node,
+        };
+        let (size, children) = match node {
+            Inline::Text { literal } => {
+                escape_html_into(out, literal);
+                continue;
+            }
+            Inline::Code { literal } => {
+                out.push_str("<code>");
+                escape_html_into(out, literal);
+                out.push_str("</code>");
+                continue;
+            }
+            Inline::RawHtml { literal } => {
+                push_raw_html(out, literal, opts, open_tags);
+                continue;
+            }
+            Inline::Autolink { url, is_email } => {
+                out.push_str("<a href=\"");
+                if *is_email {
+                    out.push_str("mailto:");
+                    encode_url_escaped_into(out, url);
+                } else {
+                    encode_url_escaped_into(out, filter_url(url, false, opts));
+                }
+                out.push_str("\">");
+                escape_html_into(out, url);
+                out.push_str("</a>");
+                continue;
+            }
+            Inline::Math { literal, display } => {
+                push_math(out, literal, *display, h);
+                continue;
+            }
+            Inline::FootnoteReference { label } => {
+                if !footnotes.push_reference(out, label) {
+                    out.push_str("[^");
+                    escape_html_into(out, label);
+                    out.push(']');
+                }
+                continue;
+            }
+            Inline::Emoji { literal, .. } => {
+                escape_html_into(out, literal);
+                continue;
+            }
+            Inline::CustomEmoji { name, url } => {
+                push_custom_emoji(out, name, url, opts);
+                continue;
+            }
+            Inline::HardBreak => {
+                out.push_str("<br />\n");
+                continue;
+            }
+            Inline::SoftBreak => {
+                if opts.hard_breaks {
+                    out.push_str("<br />\n");
+                } else {
+                    out.push('\n');
+                }
+                continue;
+            }
+            Inline::Image {
+                dest,
+                title,
+                children,
+                attrs,
+            } => {
+                let alt = inline_plain_text(children);
+                let dest = filter_url(dest, true, opts);
+                if h.image(out, dest, title.as_deref(), &alt) {
+                    continue;
+                }
+                out.push_str("<img src=\"");
+                encode_url_escaped_into(out, dest);
+                out.push_str("\" alt=\"");
+                escape_html_into(out, &alt);
+                out.push('"');
+                if let Some(t) = title {
+                    out.push_str(" title=\"");
+                    escape_html_into(out, t);
+                    out.push('"');
+                }
+                push_attributes(out, attrs, IMAGE_ATTRIBUTES, true, opts);
+                out.push_str(" />");
+                continue;
+            }
+            Inline::Link {
+                dest,
+                title,
+                children,
+            } => {
+                let dest = filter_url(dest, false, opts);
+                if !h.link_open(out, dest, title.as_deref()) {
+                    out.push_str("<a href=\"");
+                    encode_url_escaped_into(out, dest);
+                    out.push('"');
+                    if let Some(t) = title {
+                        out.push_str(" title=\"");
+                        escape_html_into(out, t);
+                        out.push('"');
+                    }
+                    out.push('>');
+                }
+                stack.push(InlineWork::CloseLink);
+                stack.extend(children.iter().rev().map(InlineWork::Node));
+                continue;
+            }
+            Inline::Emphasis { children } => (1, children),
+            Inline::Strong { children } => (2, children),
+            Inline::Strikethrough { children } => (3, children),
+            Inline::Highlight { children } => (4, children),
+            Inline::Underline { children } => (5, children),
+            Inline::Superscript { children } => (6, children),
+            Inline::Subscript { children } => (7, children),
+        };
+        out.push_str(EM_OPEN[size]);
+        stack.push(InlineWork::Close(EM_CLOSE[size]));
+        stack.extend(children.iter().rev().map(InlineWork::Node));
+    }
+}
+
+/// Flattens inline content to plain text, as used for image `alt` attributes.
+pub(crate) fn inline_plain_text(inlines: &[Inline]) -> String {
+    let mut s = String::new();
+    let mut stack: Vec<&Inline> = inlines.iter().rev().collect();
+    while let Some(node) = stack.pop() {
+        match node {
+            Inline::Text { literal }
+            | Inline::Code { literal }
+            | Inline::Math { literal, .. }
+            | Inline::Emoji { literal, .. } => s.push_str(literal),
+            Inline::CustomEmoji { name, .. } => {
+                s.push(':');
+                s.push_str(name);
+                s.push(':');
             }
-        }
-        LinkDest::Owned(d) => {
-            crate::html::encode_url_escaped_into(out, d);
+            Inline::Emphasis { children }
+            | Inline::Strong { children }
+            | Inline::Strikethrough { children }
+            | Inline::Highlight { children }
+            | Inline::Underline { children }
+            | Inline::Superscript { children }
+            | Inline::Subscript { children }
+            | Inline::Link { children, .. }
+            | Inline::Image { children, .. } => stack.extend(children.iter().rev()),
+            Inline::RawHtml { .. }
+            | Inline::Autolink { .. }
+            | Inline::FootnoteReference { .. }
+            | Inline::HardBreak
+            | Inline::SoftBreak => {}
         }
     }
+    s
 }
@@ -3,12 +3,17 @@ use super::*;
 impl<'a> InlineScanner<'a> {
     pub(super) fn scan_all(&mut self) {
         let mut text_start = self.pos;
+        let special = if self.opts.smart_punctuation {
+            &SPECIAL_SMART
+        } else {
+            &SPECIAL
+        };
 
         while self.pos < self.bytes.len() {
             let b = self.bytes[self.pos];
-            if SPECIAL[b as usize] == 0 {
+            if special[b as usize] == 0 {
                 self.pos += 1;
-                while self.pos < self.bytes.len() && SPECIAL[self.bytes[self.pos] as usize] == 0 {
+                while self.pos < self.bytes.len() && special[self.bytes[self.pos] as usize] == 0 {
                     self.pos += 1;
                 }
                 continue;
@@ -58,14 +63,62 @@ impl<'a> InlineScanner<'a> {
                     self.scan_delim_run(b);
                     text_start = self.pos;
                 }
-                b'~' | b'=' | b'+' => {
+                // Only reached with `smart_punctuation`; see `SPECIAL_SMART`.
+                b'\'' | b'"' => {
+                    self.flush_text_range(text_start, self.pos);
+                    self.scan_quote(b);
+                    text_start = self.pos;
+                }
+                b'-' => {
+                    let run = self.bytes[self.pos..]
+                        .iter()
+                        .take_while(|&&c| c == b'-')
+                        .count();
+                    if run > 1 {
+                        self.flush_text_range(text_start, self.pos);
+                        self.items.push(smart_dashes(run));
+                        text_start = self.pos + run;
+                    }
+                    self.pos += run;
+                }
+                b'.' => {
+                    if self.bytes[self.pos..].starts_with(b"...") {
+                        self.flush_text_range(text_start, self.pos);
+                        self.items.push(InlineItem::TextStatic("…"));
+                        self.pos += 3;
+                        text_start = self.pos;
+                    } else {
+                        self.pos += 1;
+                    }
+                }
+                b'$' => {
+                    if let Some((start, end, next, display)) = self.math_span() {
+                        self.flush_text_range(text_start, self.pos);
+                        self.items
+                            .push(InlineItem::Math(start as u32, end as u32, display));
+                        self.pos = next;
+                        text_start = self.pos;
+                    } else {
+                        self.pos += 1;
+                    }
+                }
+                b'~' | b'=' | b'+' | b'^' => {
+                    let double = self.bytes.get(self.pos + 1) == Some(&b);
+                    // A lone `~` is subscript, a run of two or more strikethrough.
+                    let single = !double && (self.pos == 0 || self.bytes[self.pos - 1] != b);
                     let enabled = match b {
+                        b'~' if single => self.opts.enable_subscript,
                         b'~' => self.opts.enable_strikethrough,
                         b'=' => self.opts.enable_highlight,
                         b'+' => self.opts.enable_underline,
+                        b'^' => single && self.opts.enable_superscript,
                         _ => false,
                     };
-                    if enabled && self.pos + 1 < self.bytes.len() && self.bytes[self.pos + 1] == b {
+                    if enabled && single {
+                        self.flush_text_range(text_start, self.pos);
+                        self.scan_script_delim(b);
+                        text_start = self.pos;
+                    } else if enabled && double {
                         self.flush_text_range(text_start, self.pos);
                         self.scan_delim_run(b);
                         text_start = self.pos;
@@ -74,7 +127,10 @@ impl<'a> InlineScanner<'a> {
                     }
                 }
                 b'!' => {
-                    if self.pos + 1 < self.bytes.len() && self.bytes[self.pos + 1] == b'[' {
+                    if self.pos + 1 < self.bytes.len()
+                        && self.bytes[self.pos + 1] == b'['
+                        && !self.brackets_full()
+                    {
                         self.flush_text_range(text_start, self.pos);
                         let idx = self.items.len();
                         self.items.push(InlineItem::BracketOpen { is_image: true });
@@ -91,8 +147,17 @@ impl<'a> InlineScanner<'a> {
                         self.pos += 1;
                     }
                 }
+                b'[' if self.brackets_full() => self.pos += 1,
                 b'[' => {
                     self.flush_text_range(text_start, self.pos);
+                    if let Some(end) = self.footnote_label_end() {
+                        let start = self.pos + 2;
+                        self.items
+                            .push(InlineItem::FootnoteRef(start as u32, end as u32));
+                        self.pos = end + 1;
+                        text_start = self.pos;
+                        continue;
+                    }
                     let idx = self.items.len();
                     self.items.push(InlineItem::BracketOpen { is_image: false });
                     self.pos += 1;
@@ -149,7 +214,9 @@ impl<'a> InlineScanner<'a> {
                     text_start = self.pos;
                 }
                 b':' => {
-                    if self.opts.enable_autolink && self.try_bare_url(text_start) {
+                    if (self.opts.enable_autolink && self.try_bare_url(text_start))
+                        || (self.opts.enable_emoji && self.try_emoji(text_start))
+                    {
                         text_start = self.pos;
                     } else {
                         self.pos += 1;
@@ -253,6 +320,11 @@ impl<'a> InlineScanner<'a> {
             count += 1;
             self.pos += 1;
         }
+        if self.delims.len() >= self.max_delims {
+            *self.limit_hit = true;
+            self.flush_text_range(run_start, self.pos);
+            return;
+        }
 
         let before = if run_start > 0 {
             char_before(self.input, run_start)
@@ -278,6 +350,161 @@ impl<'a> InlineScanner<'a> {
         self.delims.push(idx);
     }
 
+    /// A lone `^` or `~`. As in Pandoc, it only opens superscript or
+    /// subscript if another lone marker follows with no whitespace in between.
+    fn scan_script_delim(&mut self, marker: u8) {
+        let start = self.pos;
+        self.pos += 1;
+        let rest = &self.bytes[self.pos..];
+        let mut can_open = false;
+        let mut i = 0;
+        while i < rest.len() {
+            match rest[i] {
+                b' ' | b'\t' | b'\n' => break,
+                b'\\' if rest.get(i + 1).is_some_and(|&n| is_ascii_punctuation(n)) => i += 2,
+                b if b == marker => {
+                    can_open = i > 0 && rest.get(i + 1) != Some(&marker);
+                    break;
+                }
+                _ => i += 1,
+            }
+        }
+        let can_close = start > 0 && !char_before(self.input, start).is_whitespace();
+        if !(can_open || can_close) {
+            self.flush_text_range(start, self.pos);
+            return;
+        }
+        if self.delims.len() >= self.max_delims {
+            *self.limit_hit = true;
+            self.flush_text_range(start, self.pos);
+            return;
+        }
+        let idx = self.items.len();
+        self.items.push(InlineItem::DelimRun {
+            kind: marker,
+            count: 1,
+            can_open,
+            can_close,
+            open_em: SmallEmVec::new(),
+            close_em: SmallEmVec::new(),
+        });
+        self.delims.push(idx);
+    }
+
+    /// A `'` or `"` with `smart_punctuation`. Quotes that can open or close
+    /// are delimiters, paired up like emphasis in `process_emphasis`.
+    fn scan_quote(&mut self, quote: u8) {
+        let start = self.pos;
+        self.pos += 1;
+        let before = char_before(self.input, start);
+        let after = char_at(self.input, self.pos);
+        let (can_open, can_close) = flanking(quote, before, after);
+        if !(can_open || can_close) || self.delims.len() >= self.max_delims {
+            *self.limit_hit |= can_open || can_close;
+            let mut text = String::new();
+            push_delim_text(&mut text, quote, 1, can_close);
+            self.items.push(InlineItem::TextOwned(text));
+            return;
+        }
+        let idx = self.items.len();
+        self.items.push(InlineItem::DelimRun {
+            kind: quote,
+            count: 1,
+            can_open,
+            can_close,
+            open_em: SmallEmVec::new(),
+            close_em: SmallEmVec::new(),
+        });
+        self.delims.push(idx);
+    }
+
+    /// Math starting at the `$` at `pos`: `$…$`, `` $`…`$ `` or `$$…$$`.
+    /// Returns the content range, the offset past the closing delimiter and
+    /// whether it is display math.
+    ///
+    /// Like GitHub, `$…$` must not start or end with whitespace, and the
+    /// closing `$` must not be followed by a digit, so `$5 and $10` stays text.
+    fn math_span(&mut self) -> Option<(usize, usize, usize, bool)> {
+        if !self.opts.enable_math {
+            return None;
+        }
+        let bytes = self.bytes;
+        let start = self.pos;
+        let run = |from: usize| bytes[from..].iter().take_while(|&&b| b == b'`').count();
+        match *bytes.get(start + 1)? {
+            b'`' => {
+                let ticks = run(start + 1);
+                let open_end = start + 1 + ticks;
+                let mut i = open_end;
+                while let Some(off) = memchr::memchr(b'`', &bytes[i..]) {
+                    let close = i + off;
+                    let close_end = close + run(close);
+                    if close_end - close == ticks && bytes.get(close_end) == Some(&b'$') {
+                        return Some((open_end, close, close_end + 1, false));
+                    }
+                    i = close_end;
+                }
+                None
+            }
+            b'$' => {
+                let open_end = start + 2;
+                if open_end >= self.display_math_no_close {
+                    return None;
+                }
+                let Some(off) = memchr::memmem::find(&bytes[open_end..], b"$$") else {
+                    self.display_math_no_close = open_end;
+                    return None;
+                };
+                let close = open_end + off;
+                (close > open_end).then_some((open_end, close, close + 2, true))
+            }
+            b if b.is_ascii_whitespace() => None,
+            _ => {
+                let open_end = start + 1;
+                if open_end >= self.math_no_close {
+                    return None;
+                }
+                let mut i = open_end + 1;
+                while let Some(off) = memchr::memchr(b'$', &bytes[i..]) {
+                    let close = i + off;
+                    let before = bytes[close - 1];
+                    if !before.is_ascii_whitespace()
+                        && before != b'\\'
+                        && !bytes.get(close + 1).is_some_and(u8::is_ascii_digit)
+                    {
+                        return Some((open_end, close, close + 1, false));
+                    }
+                    i = close + 1;
+                }
+                self.math_no_close = open_end;
+                None
+            }
+        }
+    }
+
+    /// End of the label if a `[^label]` reference to a defined footnote
+    /// starts at `pos`.
+    fn footnote_label_end(&self) -> Option<usize> {
+        if !self.opts.enable_footnotes || self.bytes.get(self.pos + 1) != Some(&b'^') {
+            return None;
+        }
+        let start = self.pos + 2;
+        let end = start + memchr::memchr(b']', &self.bytes[start..])?;
+        let label = &self.input[start..end];
+        (is_footnote_label(label) && self.footnotes.is_defined(label)).then_some(end)
+    }
+
+    /// Whether another bracket would exceed `max_inline_delimiters`; records
+    /// the hit if so.
+    #[inline]
+    fn brackets_full(&mut self) -> bool {
+        if self.brackets.len() < self.max_delims {
+            return false;
+        }
+        *self.limit_hit = true;
+        true
+    }
+
     pub(super) fn handle_close_bracket(&mut self) {
         if self.brackets.is_empty() {
             self.items.push(InlineItem::TextStatic("]"));
@@ -328,11 +555,20 @@ impl<'a> InlineScanner<'a> {
         }
         self.brackets.truncate(bi);
         self.process_emphasis(delim_bottom);
+        let mut attrs = None;
+        if is_image
+            && self.opts.enable_attributes
+            && let Some((parsed, len)) = parse_attributes(&self.input[self.pos..])
+        {
+            self.pos += len;
+            attrs = Some(Box::new(parsed));
+        }
         let link_idx = self.links.len() as u16;
         self.links.push(LinkInfo {
             dest,
             title,
             is_image,
+            attrs,
         });
         self.items[opener_item] = InlineItem::LinkStart(link_idx);
         self.items.push(InlineItem::LinkEnd);
@@ -398,7 +634,11 @@ impl<'a> InlineScanner<'a> {
                 {
                     continue;
                 }
-                if matches!(ckind, b'~' | b'=' | b'+') && (ocount < 2 || ccount < 2) {
+                if matches!(ckind, b'=' | b'+') && (ocount < 2 || ccount < 2) {
+                    continue;
+                }
+                // `~` pairs with `~` (subscript), `~~` with `~~` (strikethrough).
+                if ckind == b'~' && (ocount < 2 || ccount < 2) && (ocount, ccount) != (1, 1) {
                     continue;
                 }
                 found = Some(odi);
@@ -413,6 +653,20 @@ impl<'a> InlineScanner<'a> {
             let oi = self.delims[opener_di];
             let ci = self.delims[closer_di];
 
+            if matches!(ckind, b'\'' | b'"') {
+                let (open, close) = if ckind == b'"' {
+                    ("“", "”")
+                } else {
+                    ("‘", "’")
+                };
+                self.items[oi] = InlineItem::TextStatic(open);
+                self.items[ci] = InlineItem::TextStatic(close);
+                self.delims[opener_di] = usize::MAX;
+                self.delims[closer_di] = usize::MAX;
+                closer_di += 1;
+                continue;
+            }
+
             let ocount = match &self.items[oi] {
                 InlineItem::DelimRun { count, .. } => *count,
                 _ => 0,
@@ -422,17 +676,14 @@ impl<'a> InlineScanner<'a> {
                 _ => 0,
             };
 
-            let is_ext = matches!(ckind, b'~' | b'=' | b'+');
             let use_count: u16 = if ocount >= 2 && ccount >= 2 { 2 } else { 1 };
-            let tag_size: u8 = if is_ext {
-                match ckind {
-                    b'~' => 3, // <del>
-                    b'=' => 4, // <mark>
-                    b'+' => 5, // <u>
-                    _ => use_count as u8,
-                }
-            } else {
-                use_count as u8
+            let tag_size: u8 = match ckind {
+                b'~' if use_count == 1 => 7, // <sub>
+                b'~' => 3,                   // <del>
+                b'=' => 4,                   // <mark>
+                b'+' => 5,                   // <u>
+                b'^' => 6,                   // <sup>
+                _ => use_count as u8,
             };
 
             if let InlineItem::DelimRun { count, open_em, .. } = &mut self.items[oi] {
@@ -495,6 +746,9 @@ impl<'a> InlineScanner<'a> {
             return false;
         };
 
+        if scheme_start < text_start {
+            return false;
+        }
         if scheme_start > 0 {
             let prev = bytes[scheme_start - 1];
             if prev.is_ascii_alphanumeric() || prev == b'_' {
@@ -572,8 +826,10 @@ impl<'a> InlineScanner<'a> {
             return false;
         }
 
+        // Stop at `text_start`: anything before it is already in `items`, such
+        // as a smart dash or an emphasis delimiter.
         let mut local_start = at_pos;
-        while local_start > 0 && is_email_local_char(bytes[local_start - 1]) {
+        while local_start > text_start && is_email_local_char(bytes[local_start - 1]) {
             local_start -= 1;
         }
 
@@ -626,4 +882,62 @@ impl<'a> InlineScanner<'a> {
         self.pos = end;
         true
     }
+    /// `:name:` emoji shortcode. Neither colon may touch a letter or digit
+    /// on the outside, and the name must be in the built-in table or known to
+    /// `custom_emoji`.
+    fn try_emoji(&mut self, text_start: usize) -> bool {
+        let bytes = self.bytes;
+        let start = self.pos + 1;
+        if self.input[..self.pos]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+        {
+            return false;
+        }
+        let mut end = start;
+        while end < bytes.len()
+            && (bytes[end].is_ascii_alphanumeric() || matches!(bytes[end], b'_' | b'+' | b'-'))
+        {
+            end += 1;
+        }
+        if end == start
+            || bytes.get(end) != Some(&b':')
+            || self.input[end + 1..]
+                .chars()
+                .next()
+                .is_some_and(char::is_alphanumeric)
+        {
+            return false;
+        }
+        let name = &self.input[start..end];
+        let item = if let Some(emoji) = lookup_emoji(name) {
+            InlineItem::Emoji(start as u32, end as u32, emoji)
+        } else if let Some(url) = self.opts.custom_emoji.as_ref().and_then(|c| c.url(name)) {
+            InlineItem::CustomEmoji(start as u32, end as u32, url)
+        } else {
+            return false;
+        };
+        self.flush_text_range(text_start, self.pos);
+        self.items.push(item);
+        self.pos = end + 1;
+        true
+    }
+}
+
+/// cmark's `--smart` dashes: a run of hyphens becomes em dashes if its
+/// length is a multiple of three, else en dashes if a multiple of two, else
+/// em dashes followed by one or two en dashes.
+fn smart_dashes(run: usize) -> InlineItem {
+    let (em, en) = match (run % 3, run % 2) {
+        (0, _) => (run / 3, 0),
+        (_, 0) => (0, run / 2),
+        (2, _) => ((run - 2) / 3, 1),
+        _ => ((run - 4) / 3, 2),
+    };
+    match (em, en) {
+        (0, 1) => InlineItem::TextStatic("–"),
+        (1, 0) => InlineItem::TextStatic("—"),
+        _ => InlineItem::TextOwned("—".repeat(em) + &"–".repeat(en)),
+    }
 }
@@ -0,0 +1,199 @@
+use super::*;
+use crate::ast::Inline;
+use crate::html::unescape_html;
+
+#[derive(Clone, Copy)]
+enum Frame {
+    Root,
+    Em(u8),
+    Link(u16),
+}
+
+impl<'a> InlineScanner<'a> {
+    /// Builds the inline tree from the scanned items. Must run after `process_emphasis`.
+    pub(super) fn build_inlines(&self) -> Vec<Inline> {
+        let mut stack: Vec<(Frame, Vec<Inline>)> = Vec::with_capacity(8);
+        stack.push((Frame::Root, Vec::new()));
+
+        for item in self.items.iter() {
+            match item {
+                InlineItem::TextRange(start, end) => {
+                    push_text(&mut stack, &self.input[*start..*end]);
+                }
+                InlineItem::TextOwned(t) => push_text(&mut stack, &unescape_html(t)),
+                InlineItem::TextStatic(t) => push_text(&mut stack, &unescape_html(t)),
+                InlineItem::TextInline { buf, len } => {
+                    // SAFETY: `buf` is constructed from UTF-8 bytes and `len` tracks initialized prefix length.
+                    push_text(&mut stack, unsafe {
+                        std::str::from_utf8_unchecked(&buf[..*len as usize])
+                    });
+                }
+                InlineItem::RawHtml(start, end) => push_node(
+                    &mut stack,
+                    Inline::RawHtml {
+                        literal: self.input[*start..*end].to_string(),
+                    },
+                ),
+                InlineItem::Autolink(start, end, is_email) => push_node(
+                    &mut stack,
+                    Inline::Autolink {
+                        url: self.input[*start as usize..*end as usize].to_string(),
+                        is_email: *is_email,
+                    },
+                ),
+                InlineItem::Code(c) => push_node(&mut stack, Inline::Code { literal: c.clone() }),
+                InlineItem::CodeRange(s, e) => push_node(
+                    &mut stack,
+                    Inline::Code {
+                        literal: self.input[*s as usize..*e as usize].to_string(),
+                    },
+                ),
+                InlineItem::HardBreak => push_node(&mut stack, Inline::HardBreak),
+                InlineItem::SoftBreak => push_node(&mut stack, Inline::SoftBreak),
+                InlineItem::DelimRun {
+                    kind,
+                    count,
+                    can_close,
+                    open_em,
+                    close_em,
+                    ..
+                } => {
+                    for &size in close_em.as_slice() {
+                        if stack.len() > 1
+                            && matches!(stack[stack.len() - 1].0, Frame::Em(s) if s == size)
+                        {
+                            self.close_frame(&mut stack);
+                        }
+                    }
+                    if *count > 0 {
+                        let mut run = String::new();
+                        push_delim_text(&mut run, *kind, *count, *can_close);
+                        push_text(&mut stack, &run);
+                    }
+                    for &size in open_em.as_slice().iter().rev() {
+                        stack.push((Frame::Em(size), Vec::new()));
+                    }
+                }
+                InlineItem::BracketOpen { is_image } => {
+                    push_text(&mut stack, if *is_image { "![" } else { "[" });
+                }
+                InlineItem::Math(start, end, display) => push_node(
+                    &mut stack,
+                    Inline::Math {
+                        literal: self.input[*start as usize..*end as usize].to_string(),
+                        display: *display,
+                    },
+                ),
+                InlineItem::FootnoteRef(start, end) => push_node(
+                    &mut stack,
+                    Inline::FootnoteReference {
+                        label: self.input[*start as usize..*end as usize].to_string(),
+                    },
+                ),
+                InlineItem::Emoji(start, end, literal) => push_node(
+                    &mut stack,
+                    Inline::Emoji {
+                        name: self.input[*start as usize..*end as usize].to_string(),
+                        literal: literal.to_string(),
+                    },
+                ),
+                InlineItem::CustomEmoji(start, end, url) => push_node(
+                    &mut stack,
+                    Inline::CustomEmoji {
+                        name: self.input[*start as usize..*end as usize].to_string(),
+                        url: url.clone(),
+                    },
+                ),
+                InlineItem::LinkStart(link_idx) => {
+                    stack.push((Frame::Link(*link_idx), Vec::new()));
+                }
+                InlineItem::LinkEnd => {
+                    if stack.len() > 1 && matches!(stack[stack.len() - 1].0, Frame::Link(_)) {
+                        self.close_frame(&mut stack);
+                    }
+                }
+            }
+        }
+
+        while stack.len() > 1 {
+            self.close_frame(&mut stack);
+        }
+        stack
+            .pop()
+            .map(|(_, children)| children)
+            .unwrap_or_default()
+    }
+
+    fn close_frame(&self, stack: &mut Vec<(Frame, Vec<Inline>)>) {
+        let Some((frame, children)) = stack.pop() else {
+            return;
+        };
+        let node = match frame {
+            Frame::Root => return,
+            Frame::Em(1) => Inline::Emphasis { children },
+            Frame::Em(2) => Inline::Strong { children },
+            Frame::Em(3) => Inline::Strikethrough { children },
+            Frame::Em(4) => Inline::Highlight { children },
+            Frame::Em(5) => Inline::Underline { children },
+            Frame::Em(6) => Inline::Superscript { children },
+            Frame::Em(7) => Inline::Subscript { children },
+            Frame::Em(_) => Inline::Emphasis { children },
+            Frame::Link(link_idx) => {
+                let LinkInfo {
+                    dest,
+                    title,
+                    is_image,
+                    attrs,
+                } = &self.links[link_idx as usize];
+                let dest = link_dest_str(dest, self.input).to_string();
+                let title = title.as_deref().map(str::to_string);
+                if *is_image {
+                    Inline::Image {
+                        dest,
+                        title,
+                        children,
+                        attrs: attrs.as_deref().cloned().unwrap_or_default(),
+                    }
+                } else {
+                    Inline::Link {
+                        dest,
+                        title,
+                        children,
+                    }
+                }
+            }
+        };
+        push_node(stack, node);
+    }
+}
+
+#[inline]
+pub(super) fn link_dest_str<'s>(dest: &'s LinkDest, input: &'s str) -> &'s str {
+    match dest {
+        LinkDest::Range(s, e) => &input[*s as usize..*e as usize],
+        LinkDest::Owned(d) => d,
+    }
+}
+
+#[inline]
+fn push_node(stack: &mut [(Frame, Vec<Inline>)], node: Inline) {
+    if let Some((_, children)) = stack.last_mut() {
+        children.push(node);
+    }
+}
+
+fn push_text(stack: &mut [(Frame, Vec<Inline>)], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let Some((_, children)) = stack.last_mut() else {
+        return;
+    };
+    if let Some(Inline::Text { literal }) = children.last_mut() {
+        literal.push_str(text);
+    } else {
+        children.push(Inline::Text {
+            literal: text.to_string(),
+        });
+    }
+}
@@ -36,14 +36,35 @@
 //! | newlines | `<br />` | `hard_breaks` |
 
 pub mod ast;
+mod attributes;
 mod block;
+mod emoji;
 mod entities;
+mod front_matter;
+mod handler;
 mod html;
 mod inline;
+mod markdown;
 mod render;
+mod sanitize;
+mod toc;
 
-pub use ast::{Block, ListKind, TableAlignment, TableData};
-pub use block::{parse, parse_to_ast};
+pub use ast::{
+    AlertKind, Attributes, Block, Inline, LinkDefinition, LinkReferences, ListKind, Position,
+    SourcePos, TableAlignment, TableCell, TableData,
+};
+pub use block::{
+    parse, parse_to_ast, parse_to_ast_with_front_matter, parse_to_ast_with_refs,
+    parse_with_front_matter, parse_with_handler, try_parse,
+};
+pub use front_matter::{FrontMatter, FrontMatterKind, ParseOutput};
+pub use handler::{CodeHighlighter, CustomEmoji, HtmlHandler};
+pub use markdown::{
+    FormatOptions, HeadingStyle, OrderedNumbering, format, to_markdown, to_markdown_with,
+};
+pub use render::{render_html, render_html_with_handler, render_html_with_refs};
+pub use sanitize::HtmlAllowlist;
+pub use toc::{TocEntry, render_toc, toc};
 
 #[inline(always)]
 pub(crate) fn is_ascii_punctuation(b: u8) -> bool {
@@ -63,6 +84,59 @@ pub(crate) fn utf8_char_len(first: u8) -> usize {
     }
 }
 
+/// How raw HTML in the source (HTML blocks and inline tags) is rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RawHtmlMode {
+    /// Write raw HTML to the output unchanged.
+    #[default]
+    PassThrough,
+    /// Escape raw HTML so it shows up as literal text. HTML blocks become paragraphs.
+    Escape,
+    /// Drop raw HTML from the output.
+    Omit,
+    /// Keep only the tags and attributes allowed by
+    /// [`ParseOptions::html_allowlist`]; see [`HtmlAllowlist`].
+    Sanitize,
+}
+
+/// A resource limit from [`ParseOptions`] that was exceeded, returned by
+/// [`try_parse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input is larger than [`ParseOptions::max_input_size`].
+    InputTooLarge { size: usize, limit: usize },
+    /// Block quotes, list items, footnote definitions, containers and
+    /// definition list details are nested deeper than
+    /// [`ParseOptions::max_nesting_depth`].
+    NestingTooDeep { limit: usize },
+    /// A paragraph has more delimiter runs or brackets than
+    /// [`ParseOptions::max_inline_delimiters`].
+    TooManyDelimiters { limit: usize },
+    /// The HTML would be larger than [`ParseOptions::max_output_size`].
+    OutputTooLarge { limit: usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InputTooLarge { size, limit } => {
+                write!(f, "input is {size} bytes, limit is {limit}")
+            }
+            ParseError::NestingTooDeep { limit } => {
+                write!(f, "blocks are nested more than {limit} levels deep")
+            }
+            ParseError::TooManyDelimiters { limit } => {
+                write!(f, "more than {limit} inline delimiters or brackets")
+            }
+            ParseError::OutputTooLarge { limit } => {
+                write!(f, "output exceeds {limit} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Options for customizing Markdown parsing behavior.
 pub struct ParseOptions {
     /// When `true`, every newline inside a paragraph becomes a hard line break (`<br />`),
@@ -74,6 +148,13 @@ pub struct ParseOptions {
     pub enable_strikethrough: bool,
     /// Enable `++underline++` syntax → `<u>`. Default: `true`.
     pub enable_underline: bool,
+    /// Enable Pandoc's `x^2^` syntax → `<sup>`. The text between the carets
+    /// cannot contain whitespace. Default: `false`.
+    pub enable_superscript: bool,
+    /// Enable Pandoc's `H~2~O` syntax → `<sub>`. The text between the tildes
+    /// cannot contain whitespace; `~~double~~` tildes stay strikethrough.
+    /// Default: `false`.
+    pub enable_subscript: bool,
     /// Enable pipe table syntax. Default: `true`.
     pub enable_tables: bool,
     /// Automatically detect bare URLs (`https://...`) and emails (`user@example.com`)
@@ -82,6 +163,117 @@ pub struct ParseOptions {
     /// Enable GitHub-style task lists (`- [ ] unchecked`, `- [x] checked`)
     /// in list items. Default: `true`.
     pub enable_task_lists: bool,
+    /// Enable GFM footnotes: `[^label]` references and `[^label]: text`
+    /// definitions, rendered as a numbered `<section class="footnotes">` with
+    /// back links at the end of the document. Default: `false`.
+    pub enable_footnotes: bool,
+    /// Recognize `$inline$`, `` $`inline`$ `` and `$$display$$` math, `$$`
+    /// blocks and ```` ```math ```` fences. Their content is kept raw (only
+    /// HTML-escaped) in `<span class="math inline">` and
+    /// `<div class="math display">`, for a client-side renderer such as KaTeX;
+    /// see also [`HtmlHandler::math`]. Default: `false`.
+    pub enable_math: bool,
+    /// Render top-level block quotes starting with a `> [!NOTE]`, `[!TIP]`,
+    /// `[!IMPORTANT]`, `[!WARNING]` or `[!CAUTION]` line as GitHub alerts
+    /// (`<div class="markdown-alert markdown-alert-note">` with a
+    /// `<p class="markdown-alert-title">`). Default: `false`.
+    pub enable_alerts: bool,
+    /// Parse `:::name attributes` … `:::` fenced containers, which can hold any
+    /// blocks and nest when the outer fence has more colons. Rendered as
+    /// `<div class="name">`; see [`HtmlHandler::container_open`]. Default: `false`.
+    pub enable_containers: bool,
+    /// Turn a paragraph followed by `: definition` lines into a `<dl>` with
+    /// the paragraph as `<dt>` and each definition as `<dd>`, as in PHP
+    /// Markdown Extra and Pandoc. Definitions can hold any blocks indented
+    /// past the `: ` marker. Default: `false`.
+    pub enable_definition_lists: bool,
+    /// Parse Pandoc-style `{#id .class key=value}` attribute blocks at the end
+    /// of ATX and setext headings (`## Install {#install .tab}`), in fenced
+    /// code info strings (```` ```rust {.numbered} ````) and right after
+    /// images (`![logo](logo.png){width=120}`). They are rendered as HTML
+    /// attributes on the heading, `<pre>` or `<img>`. Default: `false`.
+    pub enable_attributes: bool,
+    /// Typographic punctuation, like cmark's `--smart`: straight quotes become
+    /// curly quotes (`"a"` → “a”, `don't` → don’t), `--` an en dash, `---`
+    /// an em dash and `...` an ellipsis. Code, raw HTML, autolinks and link
+    /// destinations are left alone. Default: `false`.
+    pub smart_punctuation: bool,
+    /// Replace GitHub-style `:shortcode:` emoji such as `:tada:` or `:+1:`
+    /// with the Unicode character. The colons must not touch letters or
+    /// digits on the outside, and code spans, autolinks and link destinations
+    /// are left alone. Unknown names stay literal text unless
+    /// `custom_emoji` knows them. Default: `false`.
+    pub enable_emoji: bool,
+    /// Skip a YAML (`---`) or TOML (`+++`) front matter block at the very
+    /// start of the input instead of rendering it as a thematic break and
+    /// text. Use [`parse_with_front_matter`] or
+    /// [`parse_to_ast_with_front_matter`] to get its contents. Default: `false`.
+    pub front_matter: bool,
+    /// Record byte offsets and line/column ranges on every [`Block`] in the AST
+    /// (`pos` fields). [`Inline`] nodes have no positions; use the enclosing
+    /// block's. Default: `false`.
+    pub source_positions: bool,
+    /// Add `data-sourcepos="line:col-line:col"` attributes to block-level HTML
+    /// elements, like cmark's `--sourcepos`. Implies position tracking. Default: `false`.
+    pub sourcepos: bool,
+    /// Give every heading an `id` attribute with a GitHub-style slug of its text
+    /// (`## Hello, World!` → `hello-world`); repeated slugs get `-1`, `-2`, …
+    /// appended. The slugs are also set on [`Block::Heading`]'s `id`. Default: `false`.
+    pub heading_ids: bool,
+    /// Add an empty `<a class="anchor" href="#slug" aria-hidden="true"></a>`
+    /// self-link at the start of every heading. Implies `heading_ids`. Default: `false`.
+    pub heading_anchors: bool,
+    /// Replace a paragraph consisting only of `[[toc]]` or `[TOC]` with a
+    /// `<nav class="toc">` table of contents (see [`render_toc`]). Implies
+    /// `heading_ids`. Default: `false`.
+    pub toc_placeholder: bool,
+    /// What to do with raw HTML; use [`RawHtmlMode::Escape`],
+    /// [`RawHtmlMode::Omit`] or [`RawHtmlMode::Sanitize`] for untrusted
+    /// input. Default: [`RawHtmlMode::PassThrough`].
+    pub raw_html: RawHtmlMode,
+    /// Tags and attributes kept by [`RawHtmlMode::Sanitize`]. `None` uses
+    /// [`HtmlAllowlist::default`]. Default: `None`.
+    pub html_allowlist: Option<HtmlAllowlist>,
+    /// GFM "disallowed raw HTML": escape the `<` of `<title>`, `<textarea>`,
+    /// `<style>`, `<xmp>`, `<iframe>`, `<noembed>`, `<noframes>`, `<script>`
+    /// and `<plaintext>` tags in passed-through raw HTML, leaving other HTML
+    /// intact. Default: `false`.
+    pub tagfilter: bool,
+    /// Replace link and image URLs whose scheme is not in `allowed_url_schemes`
+    /// (such as `javascript:` or `data:`) with an empty string. Applies to
+    /// inline and reference links, images and autolinks. Default: `false`.
+    pub safe_urls: bool,
+    /// Schemes accepted when `safe_urls` is on, compared case-insensitively.
+    /// URLs without a scheme are always accepted, and images also accept
+    /// `data:image/*`. Default: `http`, `https`, `mailto`, `tel` and `ftp`.
+    pub allowed_url_schemes: Vec<String>,
+    /// Maximum input size in bytes. [`parse`] ignores everything past the
+    /// limit, [`try_parse`] fails. Default: `None` (unlimited).
+    pub max_input_size: Option<usize>,
+    /// Maximum number of nested block quotes, list items, footnote
+    /// definitions, containers and definition list details. Markers that
+    /// would nest deeper are kept as paragraph text. Default: `None`
+    /// (unlimited).
+    pub max_nesting_depth: Option<usize>,
+    /// Maximum number of emphasis delimiter runs, and separately of brackets,
+    /// tracked while parsing one paragraph, heading or table cell. Further ones
+    /// are kept as literal text. Default: `None` (unlimited).
+    pub max_inline_delimiters: Option<usize>,
+    /// Maximum HTML output size in bytes. Rendering stops before the block
+    /// that would cross the limit; open tags are still closed. Default: `None`
+    /// (unlimited).
+    pub max_output_size: Option<usize>,
+    /// Highlighter for code block contents; see [`CodeHighlighter`]. Default: `None`.
+    pub code_highlighter: Option<Box<dyn CodeHighlighter + Send + Sync>>,
+    /// Image URLs for shortcodes outside the built-in emoji table, used when
+    /// `enable_emoji` is on; see [`CustomEmoji`]. Default: `None`.
+    pub custom_emoji: Option<Box<dyn CustomEmoji + Send + Sync>>,
+}
+
+impl ParseOptions {
+    pub(crate) fn wants_heading_ids(&self) -> bool {
+        self.heading_ids || self.heading_anchors || self.toc_placeholder
+    }
 }
 
 impl Default for ParseOptions {
@@ -91,9 +283,38 @@ impl Default for ParseOptions {
             enable_highlight: true,
             enable_strikethrough: true,
             enable_underline: true,
+            enable_superscript: false,
+            enable_subscript: false,
             enable_tables: true,
             enable_autolink: true,
             enable_task_lists: true,
+            enable_footnotes: false,
+            enable_math: false,
+            enable_alerts: false,
+            enable_containers: false,
+            enable_definition_lists: false,
+            enable_attributes: false,
+            smart_punctuation: false,
+            enable_emoji: false,
+            front_matter: false,
+            source_positions: false,
+            sourcepos: false,
+            heading_ids: false,
+            heading_anchors: false,
+            toc_placeholder: false,
+            raw_html: RawHtmlMode::PassThrough,
+            html_allowlist: None,
+            tagfilter: false,
+            safe_urls: false,
+            allowed_url_schemes: ["http", "https", "mailto", "tel", "ftp"]
+                .map(String::from)
+                .to_vec(),
+            max_input_size: None,
+            max_nesting_depth: None,
+            max_inline_delimiters: None,
+            max_output_size: None,
+            code_highlighter: None,
+            custom_emoji: None,
         }
     }
 }
@@ -0,0 +1,965 @@
+use crate::ParseOptions;
+use crate::ast::{Block, Inline, ListKind, TableAlignment, TableData};
+use crate::attributes::write_attributes;
+use crate::front_matter::FrontMatterKind;
+
+/// Heading syntax written by [`format()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeadingStyle {
+    /// `# Heading` for every level.
+    #[default]
+    Atx,
+    /// Underlined (`===` / `---`) for levels 1 and 2, ATX for the rest.
+    Setext,
+}
+
+/// How [`format()`] numbers ordered list items.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OrderedNumbering {
+    /// `1.`, `2.`, `3.`, counting up from the list's start number.
+    #[default]
+    Increment,
+    /// Every item repeats the start number: `1.`, `1.`, `1.`.
+    Repeat,
+}
+
+/// Style options for [`format()`] and [`to_markdown_with`].
+pub struct FormatOptions {
+    /// Marker for bullet lists (`b'-'`, `b'*'` or `b'+'`); `None` keeps the
+    /// source marker. Default: `Some(b'-')`.
+    pub bullet: Option<u8>,
+    /// Delimiter after ordered list numbers (`b'.'` or `b')'`); `None` keeps the
+    /// source delimiter. Default: `Some(b'.')`.
+    pub ordered_delimiter: Option<u8>,
+    /// Ordered list numbering. Default: [`OrderedNumbering::Increment`].
+    pub ordered_numbering: OrderedNumbering,
+    /// Heading syntax. Default: [`HeadingStyle::Atx`].
+    pub heading_style: HeadingStyle,
+    /// Code fence character (`` b'`' `` or `b'~'`). Backtick fences switch to `~`
+    /// when the info string contains a backtick. Default: `` b'`' ``.
+    pub fence: u8,
+    /// Pad table cells so columns line up, aligned per the column's
+    /// [`TableAlignment`]. Default: `true`.
+    pub pad_tables: bool,
+    /// Re-flow paragraphs to fit this many columns (including container
+    /// prefixes); `None` keeps existing line breaks. [`format()`] ignores it
+    /// when [`ParseOptions::hard_breaks`] is set, since every line break is
+    /// then rendered. Default: `None`.
+    pub wrap_width: Option<usize>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            bullet: Some(b'-'),
+            ordered_delimiter: Some(b'.'),
+            ordered_numbering: OrderedNumbering::Increment,
+            heading_style: HeadingStyle::Atx,
+            fence: b'`',
+            pad_tables: true,
+            wrap_width: None,
+        }
+    }
+}
+
+/// Serialize a block tree back to CommonMark text.
+///
+/// The output re-parses to an equivalent tree: fences are made longer than
+/// any backtick or tilde run in the code, text is backslash-escaped where it
+/// could be mistaken for syntax, and adjacent lists of the same kind get
+/// alternating markers so they stay separate. Paragraphs, headings and table
+/// cells are written from their inline `children`, or from `raw` when
+/// `children` is empty. List markers are kept as parsed; use
+/// [`to_markdown_with`] to normalize them.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse_to_ast, to_markdown, ParseOptions};
+///
+/// let ast = parse_to_ast("Title\n=====\n\n+ a\n+ b", &ParseOptions::default());
+/// assert_eq!(to_markdown(&ast), "# Title\n\n+ a\n+ b\n");
+/// ```
+pub fn to_markdown(block: &Block) -> String {
+    to_markdown_with(
+        block,
+        &FormatOptions {
+            bullet: None,
+            ordered_delimiter: None,
+            pad_tables: false,
+            ..FormatOptions::default()
+        },
+    )
+}
+
+/// Serialize a block tree to CommonMark text in the given style.
+pub fn to_markdown_with(block: &Block, options: &FormatOptions) -> String {
+    let mut w = MarkdownWriter {
+        out: String::new(),
+        prefixes: Vec::new(),
+        opts: options,
+    };
+    w.block(block, None);
+    w.out
+}
+
+/// Reformat a Markdown document: parse it with `parse_options` and write it
+/// back in the style described by `options`.
+///
+/// Formatting never changes the rendered HTML beyond whitespace inside
+/// paragraphs, so paragraphs are only re-flowed to `wrap_width` when
+/// [`ParseOptions::hard_breaks`] is off. Reference-style links are written as
+/// inline links, since the AST keeps only their resolved destinations. With
+/// [`ParseOptions::front_matter`], front matter is copied through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{format, FormatOptions, ParseOptions};
+///
+/// let md = "* one\n* two\n\n|a|b|\n|:-|-:|\n|long|x|";
+/// assert_eq!(
+///     format(md, &ParseOptions::default(), &FormatOptions::default()),
+///     "- one\n- two\n\n| a    |   b |\n| :--- | --: |\n| long |   x |\n",
+/// );
+/// ```
+pub fn format(markdown: &str, parse_options: &ParseOptions, options: &FormatOptions) -> String {
+    let no_wrap;
+    let options = if parse_options.hard_breaks && options.wrap_width.is_some() {
+        no_wrap = FormatOptions {
+            wrap_width: None,
+            ..*options
+        };
+        &no_wrap
+    } else {
+        options
+    };
+    if !parse_options.front_matter {
+        return to_markdown_with(&crate::parse_to_ast(markdown, parse_options), options);
+    }
+    let parsed = crate::parse_to_ast_with_front_matter(markdown, parse_options);
+    let body = to_markdown_with(&parsed.body, options);
+    let Some(front_matter) = parsed.front_matter else {
+        return body;
+    };
+    let delimiter = match front_matter.kind {
+        FrontMatterKind::Yaml => "---\n",
+        FrontMatterKind::Toml => "+++\n",
+    };
+    let mut out = String::with_capacity(front_matter.raw.len() + body.len() + 10);
+    out.push_str(delimiter);
+    out.push_str(&front_matter.raw);
+    out.push_str(delimiter);
+    if !body.is_empty() {
+        out.push('\n');
+        out.push_str(&body);
+    }
+    out
+}
+
+/// One level of line prefix: `> ` for block quotes, spaces for list items.
+/// `first` replaces `indent` on the next line written (the list marker).
+struct Prefix {
+    indent: String,
+    first: Option<String>,
+}
+
+struct MarkdownWriter<'o> {
+    out: String,
+    prefixes: Vec<Prefix>,
+    opts: &'o FormatOptions,
+}
+
+impl MarkdownWriter<'_> {
+    fn line_start(&mut self) -> usize {
+        let start = self.out.len();
+        for p in self.prefixes.iter_mut() {
+            match p.first.take() {
+                Some(first) => self.out.push_str(&first),
+                None => self.out.push_str(&p.indent),
+            }
+        }
+        start
+    }
+
+    fn line(&mut self, text: &str) {
+        let start = self.line_start();
+        self.out.push_str(text);
+        if text.is_empty() {
+            let trimmed = self.out[start..].trim_end().len();
+            self.out.truncate(start + trimmed);
+        }
+        self.out.push('\n');
+    }
+
+    fn lines(&mut self, text: &str) {
+        for l in text.split('\n') {
+            self.line(l);
+        }
+    }
+
+    fn prefix_width(&self) -> usize {
+        self.prefixes.iter().map(|p| p.indent.len()).sum()
+    }
+
+    fn block(&mut self, block: &Block, list_kind: Option<ListKind>) {
+        match block {
+            Block::Document { children, .. } => self.blocks(children, false),
+            Block::Paragraph { raw, children, .. } => {
+                let mut iw = InlineWriter::new(InlineMode::Normal, self.opts.wrap_width.is_some());
+                iw.content(raw, children);
+                match self.opts.wrap_width {
+                    Some(width) => {
+                        let width = width.saturating_sub(self.prefix_width()).max(1);
+                        self.lines(&wrap(&iw.out, &iw.breaks, width));
+                    }
+                    None => self.lines(&iw.out),
+                }
+            }
+            Block::Heading {
+                level,
+                raw,
+                children,
+                attrs,
+                ..
+            } => {
+                let mut iw = InlineWriter::new(InlineMode::Normal, false);
+                iw.content(raw, children);
+                let mut text = iw.out;
+                let mut suffix = String::new();
+                if !attrs.is_empty() {
+                    suffix.push(' ');
+                    write_attributes(&mut suffix, attrs);
+                }
+                let setext = *level <= 2
+                    && (text.contains('\n')
+                        || (self.opts.heading_style == HeadingStyle::Setext && !text.is_empty()));
+                if setext {
+                    text.push_str(&suffix);
+                    self.lines(&text);
+                    let last = text.rsplit('\n').next().unwrap_or("");
+                    let underline = if *level == 1 { "=" } else { "-" };
+                    self.line(&underline.repeat(last.chars().count().max(3)));
+                } else {
+                    let mut iw = InlineWriter::new(InlineMode::SingleLine, false);
+                    iw.content(raw, children);
+                    let mut l = "#".repeat(*level as usize);
+                    if !iw.out.is_empty() {
+                        l.push(' ');
+                        l.push_str(&iw.out);
+                    }
+                    l.push_str(&suffix);
+                    self.line(&l);
+                }
+            }
+            Block::CodeBlock {
+                info,
+                literal,
+                attrs,
+                ..
+            } => {
+                let ch = if self.opts.fence == b'~' || info.contains('`') {
+                    '~'
+                } else {
+                    '`'
+                };
+                let fence = ch.to_string().repeat((longest_run(literal, ch) + 1).max(3));
+                let mut open = format!("{fence}{info}");
+                if !attrs.is_empty() {
+                    if !info.is_empty() {
+                        open.push(' ');
+                    }
+                    write_attributes(&mut open, attrs);
+                }
+                self.line(&open);
+                if !literal.is_empty() {
+                    self.lines(literal.strip_suffix('\n').unwrap_or(literal));
+                }
+                self.line(&fence);
+            }
+            Block::MathBlock { literal, .. } => {
+                self.line("$$");
+                if !literal.is_empty() {
+                    self.lines(literal);
+                }
+                self.line("$$");
+            }
+            Block::HtmlBlock { literal, .. } => {
+                self.lines(literal.strip_suffix('\n').unwrap_or(literal));
+            }
+            Block::ThematicBreak { .. } => {
+                // `* ***` would read as one thematic break rather than an item containing one.
+                let in_star_item = self
+                    .prefixes
+                    .iter()
+                    .any(|p| p.first.as_deref().is_some_and(|f| f.starts_with('*')));
+                self.line(if in_star_item { "---" } else { "***" });
+            }
+            Block::BlockQuote { children, .. } => {
+                self.prefixes.push(Prefix {
+                    indent: "> ".to_string(),
+                    first: None,
+                });
+                if children.is_empty() {
+                    self.line("");
+                }
+                self.blocks(children, false);
+                self.prefixes.pop();
+            }
+            Block::Alert { kind, children, .. } => {
+                self.prefixes.push(Prefix {
+                    indent: "> ".to_string(),
+                    first: None,
+                });
+                self.line(&format!("[!{}]", kind.as_str().to_ascii_uppercase()));
+                // Only a paragraph can follow the marker line directly; a table
+                // header, for one, would be read as paragraph continuation.
+                if !matches!(children.first(), None | Some(Block::Paragraph { .. })) {
+                    self.line("");
+                }
+                self.blocks(children, false);
+                self.prefixes.pop();
+            }
+            Block::Container {
+                name,
+                attributes,
+                children,
+                ..
+            } => {
+                // Each enclosing fence needs more colons than the ones inside it.
+                let fence = ":".repeat(3 + container_depth(children));
+                if attributes.is_empty() {
+                    self.line(&format!("{fence} {name}"));
+                } else {
+                    self.line(&format!("{fence} {name} {attributes}"));
+                }
+                self.blocks(children, false);
+                self.line(&fence);
+            }
+            Block::DefinitionList {
+                tight, children, ..
+            } => {
+                for (i, child) in children.iter().enumerate() {
+                    // A term right after a definition would continue its last paragraph.
+                    if i > 0 && (!tight || matches!(child, Block::DefinitionTerm { .. })) {
+                        self.line("");
+                    }
+                    match child {
+                        Block::DefinitionDetails { children, .. } => {
+                            self.definition_details(children, *tight)
+                        }
+                        _ => self.block(child, None),
+                    }
+                }
+            }
+            Block::DefinitionTerm { raw, children, .. } => {
+                let mut iw = InlineWriter::new(InlineMode::Normal, false);
+                iw.content(raw, children);
+                self.lines(&iw.out);
+            }
+            Block::DefinitionDetails { children, .. } => self.definition_details(children, false),
+            Block::List {
+                kind,
+                start,
+                tight,
+                children,
+                ..
+            } => {
+                let kind = list_kind.unwrap_or_else(|| self.styled_kind(*kind));
+                for (i, item) in children.iter().enumerate() {
+                    if i > 0 && !tight {
+                        self.line("");
+                    }
+                    let marker = match kind {
+                        ListKind::Bullet(c) => format!("{} ", c as char),
+                        ListKind::Ordered(d) => {
+                            let n = match self.opts.ordered_numbering {
+                                OrderedNumbering::Increment => *start as usize + i,
+                                OrderedNumbering::Repeat => *start as usize,
+                            };
+                            format!("{n}{} ", d as char)
+                        }
+                    };
+                    self.list_item(item, marker, *tight);
+                }
+            }
+            Block::ListItem { .. } => {
+                let bullet = self.opts.bullet.unwrap_or(b'-') as char;
+                self.list_item(block, format!("{bullet} "), true);
+            }
+            Block::Table(td) => self.table(td),
+            Block::FootnoteDefinition {
+                label, children, ..
+            } => {
+                self.prefixes.push(Prefix {
+                    indent: "    ".to_string(),
+                    first: Some(format!("[^{label}]: ")),
+                });
+                if children.is_empty() {
+                    self.line("");
+                }
+                self.blocks(children, false);
+                self.prefixes.pop();
+            }
+        }
+    }
+
+    fn styled_kind(&self, kind: ListKind) -> ListKind {
+        match kind {
+            ListKind::Bullet(c) => ListKind::Bullet(self.opts.bullet.unwrap_or(c)),
+            ListKind::Ordered(d) => ListKind::Ordered(self.opts.ordered_delimiter.unwrap_or(d)),
+        }
+    }
+
+    fn blocks(&mut self, children: &[Block], tight: bool) {
+        let mut prev_list: Option<ListKind> = None;
+        for (i, child) in children.iter().enumerate() {
+            if i > 0 && !tight {
+                self.line("");
+            }
+            let list_kind = match child {
+                Block::List { kind, .. } => {
+                    let styled = self.styled_kind(*kind);
+                    let kind = if prev_list == Some(styled) {
+                        alternate_kind(styled)
+                    } else {
+                        styled
+                    };
+                    prev_list = Some(kind);
+                    Some(kind)
+                }
+                _ => {
+                    prev_list = None;
+                    None
+                }
+            };
+            self.block(child, list_kind);
+        }
+    }
+
+    fn definition_details(&mut self, children: &[Block], tight: bool) {
+        self.prefixes.push(Prefix {
+            indent: "  ".to_string(),
+            first: Some(": ".to_string()),
+        });
+        if children.is_empty() {
+            self.line("");
+        }
+        self.blocks(children, tight);
+        self.prefixes.pop();
+    }
+
+    fn list_item(&mut self, item: &Block, marker: String, tight: bool) {
+        let Block::ListItem {
+            children, checked, ..
+        } = item
+        else {
+            self.block(item, None);
+            return;
+        };
+        let indent = " ".repeat(marker.len());
+        let first = match checked {
+            Some(true) => format!("{marker}[x] "),
+            Some(false) => format!("{marker}[ ] "),
+            None => marker,
+        };
+        self.prefixes.push(Prefix {
+            indent,
+            first: Some(first),
+        });
+        if children.is_empty() {
+            self.line("");
+        }
+        self.blocks(children, tight);
+        self.prefixes.pop();
+    }
+
+    fn table(&mut self, td: &TableData) {
+        let cols = td.header.len();
+        let cell_text = |cell: Option<&crate::ast::TableCell>| match cell {
+            Some(cell) => {
+                let mut iw = InlineWriter::new(InlineMode::TableCell, false);
+                iw.content(&cell.raw, &cell.children);
+                iw.out
+            }
+            None => String::new(),
+        };
+        let header: Vec<String> = td.header.iter().map(|c| cell_text(Some(c))).collect();
+        let rows: Vec<Vec<String>> = td
+            .rows
+            .iter()
+            .map(|row| (0..cols).map(|i| cell_text(row.get(i))).collect())
+            .collect();
+        let align = |i: usize| {
+            td.alignments
+                .get(i)
+                .copied()
+                .unwrap_or(TableAlignment::None)
+        };
+
+        let mut widths = vec![3; cols];
+        if self.opts.pad_tables {
+            for row in std::iter::once(&header).chain(rows.iter()) {
+                for (w, cell) in widths.iter_mut().zip(row) {
+                    *w = (*w).max(cell.chars().count());
+                }
+            }
+        }
+        let pad = self.opts.pad_tables;
+        let row_line = |cells: &[String]| {
+            let mut l = String::from("|");
+            for (i, cell) in cells.iter().enumerate() {
+                l.push(' ');
+                if pad {
+                    let fill = widths[i].saturating_sub(cell.chars().count());
+                    let (before, after) = match align(i) {
+                        TableAlignment::Right => (fill, 0),
+                        TableAlignment::Center => (fill / 2, fill - fill / 2),
+                        TableAlignment::Left | TableAlignment::None => (0, fill),
+                    };
+                    l.push_str(&" ".repeat(before));
+                    l.push_str(cell);
+                    l.push_str(&" ".repeat(after));
+                } else {
+                    l.push_str(cell);
+                }
+                l.push_str(" |");
+            }
+            l
+        };
+
+        self.line(&row_line(&header));
+        let mut delim = String::from("|");
+        for (i, &w) in widths.iter().enumerate() {
+            delim.push(' ');
+            match align(i) {
+                TableAlignment::None => delim.push_str(&"-".repeat(w)),
+                TableAlignment::Left => {
+                    delim.push(':');
+                    delim.push_str(&"-".repeat(w - 1));
+                }
+                TableAlignment::Center => {
+                    delim.push(':');
+                    delim.push_str(&"-".repeat(w - 2));
+                    delim.push(':');
+                }
+                TableAlignment::Right => {
+                    delim.push_str(&"-".repeat(w - 1));
+                    delim.push(':');
+                }
+            }
+            delim.push_str(" |");
+        }
+        self.line(&delim);
+        for row in &rows {
+            self.line(&row_line(row));
+        }
+    }
+}
+
+fn alternate_kind(kind: ListKind) -> ListKind {
+    match kind {
+        ListKind::Bullet(b'-') => ListKind::Bullet(b'*'),
+        ListKind::Bullet(_) => ListKind::Bullet(b'-'),
+        ListKind::Ordered(b'.') => ListKind::Ordered(b')'),
+        ListKind::Ordered(_) => ListKind::Ordered(b'.'),
+    }
+}
+
+/// How many levels of containers are nested inside `blocks`.
+fn container_depth(blocks: &[Block]) -> usize {
+    let mut max = 0;
+    let mut stack: Vec<(&Block, usize)> = blocks.iter().map(|b| (b, 0)).collect();
+    while let Some((block, depth)) = stack.pop() {
+        let (children, depth) = match block {
+            Block::Container { children, .. } => {
+                max = max.max(depth + 1);
+                (children, depth + 1)
+            }
+            Block::BlockQuote { children, .. }
+            | Block::Alert { children, .. }
+            | Block::List { children, .. }
+            | Block::ListItem { children, .. }
+            | Block::FootnoteDefinition { children, .. }
+            | Block::DefinitionList { children, .. }
+            | Block::DefinitionDetails { children, .. } => (children, depth),
+            _ => continue,
+        };
+        stack.extend(children.iter().map(|b| (b, depth)));
+    }
+    max
+}
+
+fn longest_run(s: &str, ch: char) -> usize {
+    let mut longest = 0;
+    let mut cur = 0;
+    for c in s.chars() {
+        if c == ch {
+            cur += 1;
+            longest = longest.max(cur);
+        } else {
+            cur = 0;
+        }
+    }
+    longest
+}
+
+/// Greedily joins the pieces of `text` between break positions (each a single
+/// space) into lines of at most `width` columns. Existing newlines (hard
+/// breaks) are kept, and a break is only taken where the next line cannot be
+/// mistaken for block syntax.
+fn wrap(text: &str, breaks: &[usize], width: usize) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut line_len = 0;
+    let mut start = 0;
+    for end in breaks.iter().copied().chain(std::iter::once(text.len())) {
+        let piece = &text[start..end];
+        if start > 0 {
+            let first_len = piece.split('\n').next().unwrap_or("").chars().count();
+            if line_len > 0 && line_len + 1 + first_len > width && safe_line_start(piece) {
+                // Two or more spaces before the newline would make it a hard
+                // break.
+                out.truncate(out.trim_end_matches(' ').len());
+                out.push('\n');
+                line_len = 0;
+            } else {
+                out.push(' ');
+                line_len += 1;
+            }
+        }
+        out.push_str(piece);
+        match piece.rfind('\n') {
+            Some(nl) => line_len = piece[nl + 1..].chars().count(),
+            None => line_len += piece.chars().count(),
+        }
+        start = end + 1;
+    }
+    out
+}
+
+fn safe_line_start(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    match bytes.first() {
+        Some(b'0'..=b'9') => {
+            let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+            !matches!(bytes.get(digits), Some(b'.' | b')'))
+        }
+        Some(b'\\' | b'(' | b'[' | b'!' | b'&' | b'"' | b'\'') => true,
+        Some(b) if b.is_ascii_alphabetic() || *b >= 0x80 => true,
+        _ => false,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InlineMode {
+    Normal,
+    /// ATX heading: line breaks become spaces.
+    SingleLine,
+    /// Table cell: single line, `|` escaped.
+    TableCell,
+}
+
+/// Writes inline content as Markdown, recording the byte offsets of spaces
+/// that [`wrap`] may turn into line breaks.
+struct InlineWriter {
+    out: String,
+    breaks: Vec<usize>,
+    mode: InlineMode,
+    /// Soft breaks become breakable spaces instead of newlines.
+    reflow: bool,
+    /// Nesting depth of image descriptions, where breaks would alter the `alt` text.
+    in_image: u32,
+}
+
+impl InlineWriter {
+    fn new(mode: InlineMode, reflow: bool) -> Self {
+        Self {
+            out: String::new(),
+            breaks: Vec::new(),
+            mode,
+            reflow,
+            in_image: 0,
+        }
+    }
+
+    fn content(&mut self, raw: &str, children: &[Inline]) {
+        if !children.is_empty() {
+            self.inlines(children);
+        } else if self.mode == InlineMode::Normal {
+            self.out.push_str(raw);
+        } else {
+            self.out.push_str(&raw.replace('\n', " "));
+        }
+    }
+
+    fn inlines(&mut self, inlines: &[Inline]) {
+        for (i, inline) in inlines.iter().enumerate() {
+            match inline {
+                Inline::Text { literal } => self.text(literal),
+                Inline::Code { literal } => self.code_span(literal),
+                // `*` directly after another `*` run would merge with it. `***x***`
+                // still parses as `<em><strong>`, so only a nested `<em>` or a
+                // sibling right after a closer switches to `_`.
+                Inline::Emphasis { children } => {
+                    let delim = if self.out.ends_with('*') { "_" } else { "*" };
+                    self.delimited(delim, children);
+                }
+                Inline::Strong { children } => {
+                    let delim = if i > 0 && self.out.ends_with('*') {
+                        "__"
+                    } else {
+                        "**"
+                    };
+                    self.delimited(delim, children);
+                }
+                Inline::Strikethrough { children } => self.delimited("~~", children),
+                Inline::Highlight { children } => self.delimited("==", children),
+                Inline::Underline { children } => self.delimited("++", children),
+                Inline::Superscript { children } => self.delimited("^", children),
+                Inline::Subscript { children } => self.delimited("~", children),
+                Inline::Link {
+                    dest,
+                    title,
+                    children,
+                } => {
+                    self.out.push('[');
+                    self.inlines(children);
+                    self.out.push_str("](");
+                    write_link_target(&mut self.out, dest, title.as_deref());
+                    self.out.push(')');
+                }
+                Inline::Image {
+                    dest,
+                    title,
+                    children,
+                    attrs,
+                } => {
+                    self.out.push_str("![");
+                    self.in_image += 1;
+                    self.inlines(children);
+                    self.in_image -= 1;
+                    self.out.push_str("](");
+                    write_link_target(&mut self.out, dest, title.as_deref());
+                    self.out.push(')');
+                    if !attrs.is_empty() {
+                        write_attributes(&mut self.out, attrs);
+                    }
+                }
+                Inline::Autolink { url, is_email } => {
+                    if *is_email || url.contains(':') {
+                        self.out.push('<');
+                        self.out.push_str(url);
+                        self.out.push('>');
+                    } else {
+                        self.out.push_str(url);
+                    }
+                }
+                Inline::RawHtml { literal } => self.out.push_str(literal),
+                Inline::Math { literal, display } => self.math(literal, *display),
+                Inline::Emoji { name, .. } | Inline::CustomEmoji { name, .. } => {
+                    self.out.push(':');
+                    self.out.push_str(name);
+                    self.out.push(':');
+                }
+                Inline::FootnoteReference { label } => {
+                    self.out.push_str("[^");
+                    self.out.push_str(label);
+                    self.out.push(']');
+                }
+                Inline::HardBreak => match self.mode {
+                    InlineMode::Normal => self.out.push_str("\\\n"),
+                    _ => self.out.push(' '),
+                },
+                Inline::SoftBreak => match self.mode {
+                    InlineMode::Normal if !self.reflow || self.in_image > 0 => self.out.push('\n'),
+                    InlineMode::Normal => {
+                        self.breaks.push(self.out.len());
+                        self.out.push(' ');
+                    }
+                    _ => self.out.push(' '),
+                },
+            }
+        }
+    }
+
+    /// `$…$`, or `` $`…`$ `` when the content itself contains a `$`.
+    fn math(&mut self, literal: &str, display: bool) {
+        let ticks = if !display && literal.contains('$') {
+            "`".repeat(longest_run(literal, '`') + 1)
+        } else {
+            String::new()
+        };
+        let delim = if display { "$$" } else { "$" };
+        self.out.push_str(delim);
+        self.out.push_str(&ticks);
+        self.out.push_str(literal);
+        self.out.push_str(&ticks);
+        self.out.push_str(delim);
+    }
+
+    fn delimited(&mut self, delim: &str, children: &[Inline]) {
+        self.out.push_str(delim);
+        self.inlines(children);
+        self.out.push_str(delim);
+    }
+
+    fn code_span(&mut self, literal: &str) {
+        let out = &mut self.out;
+        let mut ticks = 1;
+        while has_run_of(literal, '`', ticks) {
+            ticks += 1;
+        }
+        let fence = "`".repeat(ticks);
+        let pad = literal.starts_with('`')
+            || literal.ends_with('`')
+            || (literal.starts_with(' ') && literal.ends_with(' ') && literal.trim() != "");
+        out.push_str(&fence);
+        if pad {
+            out.push(' ');
+        }
+        if self.mode == InlineMode::TableCell {
+            let mut prev = '\0';
+            for c in literal.chars() {
+                if c == '|' && prev != '\\' {
+                    out.push('\\');
+                }
+                out.push(c);
+                prev = c;
+            }
+        } else {
+            out.push_str(literal);
+        }
+        if pad {
+            out.push(' ');
+        }
+        out.push_str(&fence);
+    }
+
+    fn text(&mut self, text: &str) {
+        let mode = self.mode;
+        let out = &mut self.out;
+        let bytes = text.as_bytes();
+        let mut line_start = out.is_empty() || out.ends_with('\n');
+        for (i, c) in text.char_indices() {
+            let next = bytes.get(i + 1).copied();
+            let prev = if i > 0 {
+                Some(bytes[i - 1])
+            } else {
+                out.as_bytes().last().copied()
+            };
+            let escape = match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '~' | '^' | '$' => true,
+                '&' => next.is_some_and(|n| n.is_ascii_alphanumeric() || n == b'#'),
+                '=' | '+' => next == Some(c as u8) || prev == Some(c as u8) || line_start,
+                '#' => line_start || mode != InlineMode::Normal,
+                '>' | '-' => line_start,
+                '|' => mode == InlineMode::TableCell,
+                ':' => bytes[i + 1..].starts_with(b"//") || is_shortcode(&bytes[i + 1..]),
+                '@' => true,
+                // `1.` / `1)` at the start of a line would open an ordered list.
+                '.' | ')' => line_start && prev.is_some_and(|p| p.is_ascii_digit()),
+                // `!` followed by a link would turn it into an image.
+                '!' => next.is_none(),
+                _ => false,
+            };
+            match c {
+                '\n' => out.push_str("&#10;"),
+                '\r' => out.push_str("&#13;"),
+                ' ' | '\t' if line_start && prev.is_none_or(|p| p == b'\n') => {
+                    out.push_str(if c == ' ' { "&#32;" } else { "&#9;" });
+                }
+                ' ' if mode == InlineMode::Normal && self.in_image == 0 => {
+                    self.breaks.push(out.len());
+                    out.push(' ');
+                }
+                _ => {
+                    if escape {
+                        out.push('\\');
+                    }
+                    out.push(c);
+                }
+            }
+            if !matches!(c, '0'..='9' | ' ' | '\t') {
+                line_start = c == '\n';
+            }
+        }
+    }
+}
+
+fn has_run_of(s: &str, ch: char, len: usize) -> bool {
+    let mut cur = 0;
+    for c in s.chars().chain(std::iter::once('\0')) {
+        if c == ch {
+            cur += 1;
+        } else {
+            if cur == len {
+                return true;
+            }
+            cur = 0;
+        }
+    }
+    false
+}
+
+fn write_link_target(out: &mut String, dest: &str, title: Option<&str>) {
+    let needs_angle = dest.is_empty()
+        || dest
+            .chars()
+            .any(|c| c == ' ' || c == '<' || c == '>' || c.is_control())
+        || !parens_balanced(dest);
+    if needs_angle {
+        out.push('<');
+        for c in dest.chars() {
+            if matches!(c, '<' | '>' | '\\') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out.push('>');
+    } else {
+        escape_with(out, dest, |c, next| {
+            c == '\\' || (c == '&' && next.is_some_and(|n| n.is_ascii_alphanumeric() || n == '#'))
+        });
+    }
+    if let Some(title) = title {
+        out.push_str(" \"");
+        escape_with(out, title, |c, next| {
+            matches!(c, '"' | '\\')
+                || (c == '&' && next.is_some_and(|n| n.is_ascii_alphanumeric() || n == '#'))
+        });
+        out.push('"');
+    }
+}
+
+fn parens_balanced(s: &str) -> bool {
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return false,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+/// Whether `rest` (the text after a `:`) would complete a `:name:` emoji
+/// shortcode.
+fn is_shortcode(rest: &[u8]) -> bool {
+    let name = rest
+        .iter()
+        .take_while(|&&b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'+' | b'-'))
+        .count();
+    name > 0 && rest.get(name) == Some(&b':')
+}
+
+fn escape_with(out: &mut String, s: &str, needs_escape: impl Fn(char, Option<char>) -> bool) {
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if needs_escape(c, chars.peek().copied()) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
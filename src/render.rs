@@ -1,10 +1,87 @@
-use crate::ParseOptions;
-use crate::ast::{Block, ListKind, TableAlignment};
-use crate::html::escape_html_into;
-use crate::inline::{InlineBuffers, LinkRefMap, parse_inline_pass};
+use crate::ast::{Block, Inline, LinkReferences, ListKind, SourcePos, TableAlignment, TableCell};
+use crate::attributes::push_attributes;
+use crate::handler::{DefaultHandler, HtmlHandler};
+use crate::html::{encode_url_escaped_into, escape_html_into, push_raw_html};
+use crate::inline::{InlineBuffers, LinkRefMap, parse_inline_pass, render_inlines};
+use crate::toc::{is_toc_placeholder, render_toc, toc_with_refs};
+use crate::{ParseOptions, RawHtmlMode};
 
+/// Render a block tree (typically from [`parse_to_ast`](crate::parse_to_ast)) to HTML.
+///
+/// Paragraphs, headings and table cells are rendered from their inline
+/// `children`; blocks whose `children` are empty fall back to parsing `raw`.
+/// Reference links in `raw` only resolve through
+/// [`render_html_with_refs`].
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse, parse_to_ast, render_html, ParseOptions};
+///
+/// let opts = ParseOptions::default();
+/// let ast = parse_to_ast("# Hello *world*", &opts);
+/// assert_eq!(render_html(&ast, &opts), parse("# Hello *world*", &opts));
+/// ```
+pub fn render_html(block: &Block, options: &ParseOptions) -> String {
+    render_html_with_refs(block, &LinkReferences::default(), options)
+}
+
+/// Like [`render_html`], resolving reference links in `raw` content against `refs`
+/// (see [`parse_to_ast_with_refs`](crate::parse_to_ast_with_refs)).
+pub fn render_html_with_refs(
+    block: &Block,
+    refs: &LinkReferences,
+    options: &ParseOptions,
+) -> String {
+    render_html_with_handler(block, refs, options, &mut DefaultHandler)
+}
+
+/// Like [`render_html_with_refs`], letting `handler` override the markup of
+/// individual nodes (see [`HtmlHandler`]).
+pub fn render_html_with_handler(
+    block: &Block,
+    refs: &LinkReferences,
+    options: &ParseOptions,
+    h: &mut dyn HtmlHandler,
+) -> String {
+    let mut out = String::new();
+    let mut bufs = InlineBuffers::new();
+    render_block(block, &refs.map, &mut out, options, &mut bufs, h);
+    out
+}
+
+/// Renders inline content from `children` when present, otherwise from `raw`.
 #[inline(always)]
-fn emit_checkbox(out: &mut String, checked: Option<bool>) {
+fn render_inline_content(
+    out: &mut String,
+    raw: &str,
+    children: &[Inline],
+    refs: &LinkRefMap,
+    opts: &ParseOptions,
+    bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
+) {
+    if children.is_empty() {
+        parse_inline_pass(out, raw, refs, opts, bufs, h);
+    } else {
+        render_inlines(
+            out,
+            children,
+            opts,
+            &mut bufs.footnotes,
+            &mut bufs.open_tags,
+            h,
+        );
+    }
+}
+
+#[inline(always)]
+fn emit_checkbox(out: &mut String, checked: Option<bool>, h: &mut dyn HtmlHandler) {
+    if let Some(c) = checked
+        && h.task_checkbox(out, c)
+    {
+        return;
+    }
     match checked {
         Some(true) => out.push_str("<input type=\"checkbox\" checked=\"\" disabled=\"\" /> "),
         Some(false) => out.push_str("<input type=\"checkbox\" disabled=\"\" /> "),
@@ -12,11 +89,45 @@ fn emit_checkbox(out: &mut String, checked: Option<bool>) {
     }
 }
 
+/// Writes ` data-sourcepos="l:c-l:c"` (inclusive end column, as cmark does)
+/// when `opts.sourcepos` is set and the block carries a position.
+#[inline(always)]
+fn push_sourcepos(out: &mut String, pos: Option<SourcePos>, opts: &ParseOptions) {
+    if !opts.sourcepos {
+        return;
+    }
+    if let Some(pos) = pos {
+        use std::fmt::Write;
+        let _ = write!(
+            out,
+            " data-sourcepos=\"{}:{}-{}:{}\"",
+            pos.start.line,
+            pos.start.column,
+            pos.end.line,
+            pos.end.column.saturating_sub(1).max(1)
+        );
+    }
+}
+
+/// Opens `<tag` plus any source position attribute; the caller closes with `>`.
+#[inline(always)]
+fn open_tag(out: &mut String, tag: &str, pos: Option<SourcePos>, opts: &ParseOptions) {
+    out.push_str(tag);
+    push_sourcepos(out, pos, opts);
+}
+
 enum Work<'a> {
     Block(&'a Block),
     TightListItem(&'a Block),
     TightBlock(&'a Block),
     CloseTag(&'static str),
+    /// Closes the container with this name through the handler.
+    ContainerClose(&'a str),
+    /// Opens the footnotes section after the rest of the document.
+    Footnotes,
+    /// The footnote with this index in order of first reference.
+    Footnote(usize),
+    FootnoteEnd(usize),
 }
 
 pub(crate) fn render_block(
@@ -25,27 +136,110 @@ pub(crate) fn render_block(
     out: &mut String,
     opts: &ParseOptions,
     bufs: &mut InlineBuffers,
-) {
+    h: &mut dyn HtmlHandler,
+) -> bool {
+    let toc_html = if opts.toc_placeholder {
+        let entries = toc_with_refs(block, refs, opts);
+        format!("<nav class=\"toc\">\n{}</nav>\n", render_toc(&entries))
+    } else {
+        String::new()
+    };
+    let footnotes = if opts.enable_footnotes {
+        bufs.footnotes.collect(block)
+    } else {
+        Default::default()
+    };
     let mut stack: Vec<Work<'_>> = Vec::with_capacity(32);
+    if !footnotes.is_empty() {
+        stack.push(Work::Footnotes);
+    }
     stack.push(Work::Block(block));
 
+    let max_output = opts.max_output_size.unwrap_or(usize::MAX);
+
     while let Some(work) = stack.pop() {
+        let (mark, depth, open_tags) = (out.len(), stack.len(), bufs.open_tags.len());
         match work {
-            Work::CloseTag(tag) => out.push_str(tag),
+            Work::CloseTag(tag) => {
+                out.push_str(tag);
+                continue;
+            }
+            Work::ContainerClose(name) => {
+                close_container(out, name, h);
+                continue;
+            }
+            Work::Footnotes => {
+                if !bufs.footnotes.referenced().is_empty() {
+                    out.push_str("<section class=\"footnotes\" data-footnotes>\n<ol>\n");
+                    stack.push(Work::CloseTag("</ol>\n</section>\n"));
+                    stack.push(Work::Footnote(0));
+                }
+            }
+            Work::Footnote(idx) => {
+                bufs.footnotes.push_item_open(out, idx);
+                stack.push(Work::FootnoteEnd(idx));
+                let children = footnotes[&bufs.footnotes.referenced()[idx]];
+                stack.extend(children.iter().rev().map(Work::Block));
+            }
+            Work::FootnoteEnd(idx) => {
+                // Back links go inside the last paragraph, as in GitHub's output.
+                if out.ends_with("</p>\n") {
+                    out.truncate(out.len() - "</p>\n".len());
+                    out.push(' ');
+                    bufs.footnotes.push_backrefs(out, idx);
+                    out.push_str("</p>\n");
+                } else {
+                    bufs.footnotes.push_backrefs(out, idx);
+                    out.push('\n');
+                }
+                out.push_str("</li>\n");
+                if idx + 1 < bufs.footnotes.referenced().len() {
+                    stack.push(Work::Footnote(idx + 1));
+                }
+            }
             Work::TightListItem(block) => {
-                render_tight_list_item(block, refs, out, opts, bufs, &mut stack);
+                render_tight_list_item(block, refs, out, opts, bufs, h, &mut stack);
             }
             Work::TightBlock(block) => {
-                if let Block::Paragraph { raw } = block {
-                    parse_inline_pass(out, raw, refs, opts, bufs);
+                if let Block::Paragraph { raw, children, .. } = block {
+                    render_inline_content(out, raw, children, refs, opts, bufs, h);
                 } else {
-                    render_one(block, refs, out, opts, bufs, &mut stack);
+                    render_one(block, refs, out, opts, bufs, h, &mut stack);
                 }
             }
+            Work::Block(Block::Paragraph { raw, .. })
+                if opts.toc_placeholder && is_toc_placeholder(raw) =>
+            {
+                out.push_str(&toc_html);
+            }
             Work::Block(block) => {
-                render_one(block, refs, out, opts, bufs, &mut stack);
+                render_one(block, refs, out, opts, bufs, h, &mut stack);
             }
         }
+        if out.len() > max_output {
+            // Drop the block that crossed the limit, but keep the document
+            // well-formed by closing the containers that are still open.
+            out.truncate(mark);
+            stack.truncate(depth);
+            bufs.open_tags.truncate(open_tags);
+            bufs.open_tags.close_all(out);
+            for work in stack.drain(..).rev() {
+                match work {
+                    Work::CloseTag(tag) => out.push_str(tag),
+                    Work::ContainerClose(name) => close_container(out, name, h),
+                    _ => {}
+                }
+            }
+            return false;
+        }
+    }
+    bufs.open_tags.close_all(out);
+    true
+}
+
+fn close_container(out: &mut String, name: &str, h: &mut dyn HtmlHandler) {
+    if !h.container_close(out, name) {
+        out.push_str("</div>\n");
     }
 }
 
@@ -57,20 +251,26 @@ fn list_close_tag(kind: &ListKind) -> &'static str {
 }
 
 #[inline(always)]
-fn emit_list_open(out: &mut String, kind: &ListKind, start: u32) {
+fn emit_list_open(
+    out: &mut String,
+    kind: &ListKind,
+    start: u32,
+    pos: Option<SourcePos>,
+    opts: &ParseOptions,
+) {
     match kind {
-        ListKind::Bullet(_) => out.push_str("<ul>\n"),
+        ListKind::Bullet(_) => open_tag(out, "<ul", pos, opts),
         ListKind::Ordered(_) => {
-            if start == 1 {
-                out.push_str("<ol>\n");
-            } else {
+            open_tag(out, "<ol", pos, opts);
+            if start != 1 {
                 use std::fmt::Write;
-                out.push_str("<ol start=\"");
+                out.push_str(" start=\"");
                 let _ = write!(out, "{}", start);
-                out.push_str("\">\n");
+                out.push('"');
             }
         }
     }
+    out.push_str(">\n");
 }
 
 #[inline]
@@ -80,75 +280,178 @@ fn render_one<'a>(
     out: &mut String,
     opts: &ParseOptions,
     bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
     stack: &mut Vec<Work<'a>>,
 ) {
     match block {
-        Block::Document { children } => {
+        Block::Document { children, .. } => {
             for child in children.iter().rev() {
                 stack.push(Work::Block(child));
             }
         }
-        Block::ThematicBreak => out.push_str("<hr />\n"),
-        Block::Heading { level, raw } => {
-            out.push_str("<h");
-            out.push((b'0' + level) as char);
-            out.push('>');
-            parse_inline_pass(out, raw, refs, opts, bufs);
-            out.push_str("</h");
-            out.push((b'0' + level) as char);
-            out.push_str(">\n");
-        }
-        Block::Paragraph { raw } => {
-            out.push_str("<p>");
-            parse_inline_pass(out, raw, refs, opts, bufs);
-            out.push_str("</p>\n");
+        Block::ThematicBreak { pos } => {
+            open_tag(out, "<hr", *pos, opts);
+            out.push_str(" />\n");
         }
-        Block::CodeBlock { info, literal } => {
-            out.push_str("<pre><code");
-            if !info.is_empty() {
-                let lang = info.split_whitespace().next().unwrap_or("");
-                if !lang.is_empty() {
-                    out.push_str(" class=\"language-");
-                    escape_html_into(out, lang);
+        // Rendered in the footnotes section by `render_block`.
+        Block::FootnoteDefinition { .. } => {}
+        Block::Heading {
+            level,
+            raw,
+            children,
+            id,
+            attrs,
+            pos,
+        } => {
+            // `id` is only the generated slug; an explicit one stays in `attrs`.
+            let effective_id = attrs.get("id").or(id.as_ref()).map(String::as_str);
+            if !h.heading_open(out, *level, raw, effective_id) {
+                out.push_str("<h");
+                out.push((b'0' + level) as char);
+                push_sourcepos(out, *pos, opts);
+                if let Some(id) = id {
+                    out.push_str(" id=\"");
+                    escape_html_into(out, id);
                     out.push('"');
                 }
+                push_attributes(out, attrs, &[], false, opts);
+                out.push('>');
+                if let Some(id) = effective_id.filter(|_| opts.heading_anchors) {
+                    out.push_str("<a class=\"anchor\" href=\"#");
+                    encode_url_escaped_into(out, id);
+                    out.push_str("\" aria-hidden=\"true\"></a>");
+                }
+            }
+            render_inline_content(out, raw, children, refs, opts, bufs, h);
+            if !h.heading_close(out, *level) {
+                out.push_str("</h");
+                out.push((b'0' + level) as char);
+                out.push_str(">\n");
             }
+        }
+        Block::Paragraph { raw, children, pos } => {
+            open_tag(out, "<p", *pos, opts);
             out.push('>');
-            escape_html_into(out, literal);
+            render_inline_content(out, raw, children, refs, opts, bufs, h);
+            out.push_str("</p>\n");
+        }
+        Block::CodeBlock {
+            info,
+            literal,
+            attrs,
+            pos,
+        } => {
+            if h.code_block(out, info, literal) {
+                return;
+            }
+            open_tag(out, "<pre", *pos, opts);
+            push_attributes(out, attrs, &[], false, opts);
+            out.push_str("><code");
+            let lang = info.split_whitespace().next().unwrap_or("");
+            if !lang.is_empty() {
+                out.push_str(" class=\"language-");
+                escape_html_into(out, lang);
+                out.push('"');
+            }
+            out.push('>');
+            match opts
+                .code_highlighter
+                .as_ref()
+                .and_then(|hl| hl.highlight(lang, info, literal))
+            {
+                Some(html) => out.push_str(&html),
+                None => escape_html_into(out, literal),
+            }
             out.push_str("</code></pre>\n");
         }
-        Block::HtmlBlock { literal } => {
-            out.push_str(literal);
-            if !literal.ends_with('\n') {
-                out.push('\n');
+        Block::MathBlock { literal, pos } => {
+            if h.math(out, literal, true) {
+                return;
             }
+            open_tag(out, "<div", *pos, opts);
+            out.push_str(" class=\"math display\">");
+            escape_html_into(out, literal);
+            out.push_str("</div>\n");
         }
-        Block::BlockQuote { children } => {
-            out.push_str("<blockquote>\n");
+        Block::HtmlBlock { literal, pos } => match opts.raw_html {
+            RawHtmlMode::PassThrough | RawHtmlMode::Sanitize => {
+                let start = out.len();
+                push_raw_html(out, literal, opts, &mut bufs.open_tags);
+                if out.len() > start && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+            RawHtmlMode::Escape => {
+                open_tag(out, "<p", *pos, opts);
+                out.push('>');
+                escape_html_into(out, literal.trim_end_matches('\n'));
+                out.push_str("</p>\n");
+            }
+            RawHtmlMode::Omit => {}
+        },
+        Block::BlockQuote { children, pos } => {
+            open_tag(out, "<blockquote", *pos, opts);
+            out.push_str(">\n");
             stack.push(Work::CloseTag("</blockquote>\n"));
             for child in children.iter().rev() {
                 stack.push(Work::Block(child));
             }
         }
+        Block::Alert {
+            kind,
+            children,
+            pos,
+        } => {
+            open_tag(out, "<div", *pos, opts);
+            out.push_str(" class=\"markdown-alert markdown-alert-");
+            out.push_str(kind.as_str());
+            out.push_str("\">\n<p class=\"markdown-alert-title\">");
+            out.push_str(kind.title());
+            out.push_str("</p>\n");
+            stack.push(Work::CloseTag("</div>\n"));
+            for child in children.iter().rev() {
+                stack.push(Work::Block(child));
+            }
+        }
+        Block::Container {
+            name,
+            attributes,
+            children,
+            pos,
+        } => {
+            if !h.container_open(out, name, attributes) {
+                open_tag(out, "<div", *pos, opts);
+                out.push_str(" class=\"");
+                escape_html_into(out, name);
+                out.push_str("\">\n");
+            }
+            stack.push(Work::ContainerClose(name));
+            for child in children.iter().rev() {
+                stack.push(Work::Block(child));
+            }
+        }
         Block::List {
             kind,
             start,
             tight,
             children,
+            pos,
         } => {
             if *tight && children.len() == 1 {
                 render_nested_tight_list(
                     kind,
                     *start,
+                    *pos,
                     children,
                     InlineCtx { refs, opts },
                     out,
                     bufs,
+                    h,
                     stack,
                 );
                 return;
             }
-            emit_list_open(out, kind, *start);
+            emit_list_open(out, kind, *start, *pos, opts);
             stack.push(Work::CloseTag(list_close_tag(kind)));
             if *tight {
                 for item in children.iter().rev() {
@@ -160,9 +463,14 @@ fn render_one<'a>(
                 }
             }
         }
-        Block::ListItem { children, checked } => {
-            out.push_str("<li>");
-            emit_checkbox(out, *checked);
+        Block::ListItem {
+            children,
+            checked,
+            pos,
+        } => {
+            open_tag(out, "<li", *pos, opts);
+            out.push('>');
+            emit_checkbox(out, *checked, h);
             if !children.is_empty() {
                 out.push('\n');
                 stack.push(Work::CloseTag("</li>\n"));
@@ -173,19 +481,55 @@ fn render_one<'a>(
                 out.push_str("</li>\n");
             }
         }
+        Block::DefinitionList {
+            tight,
+            children,
+            pos,
+        } => {
+            open_tag(out, "<dl", *pos, opts);
+            out.push_str(">\n");
+            stack.push(Work::CloseTag("</dl>\n"));
+            for child in children.iter().rev() {
+                stack.push(if *tight {
+                    Work::TightListItem(child)
+                } else {
+                    Work::Block(child)
+                });
+            }
+        }
+        Block::DefinitionTerm { raw, children, pos } => {
+            open_tag(out, "<dt", *pos, opts);
+            out.push('>');
+            render_inline_content(out, raw, children, refs, opts, bufs, h);
+            out.push_str("</dt>\n");
+        }
+        Block::DefinitionDetails { children, pos } => {
+            open_tag(out, "<dd", *pos, opts);
+            out.push('>');
+            if children.is_empty() {
+                out.push_str("</dd>\n");
+            } else {
+                out.push('\n');
+                stack.push(Work::CloseTag("</dd>\n"));
+                for child in children.iter().rev() {
+                    stack.push(Work::Block(child));
+                }
+            }
+        }
         Block::Table(td) => {
             let alignments = &td.alignments;
             let header = &td.header;
             let rows = &td.rows;
             let all_none = alignments.iter().all(|a| *a == TableAlignment::None);
-            out.push_str("<table>\n<thead>\n<tr>\n");
+            open_tag(out, "<table", td.pos, opts);
+            out.push_str(">\n<thead>\n<tr>\n");
             for (i, cell) in header.iter().enumerate() {
                 let align = if all_none {
                     TableAlignment::None
                 } else {
                     alignments.get(i).copied().unwrap_or(TableAlignment::None)
                 };
-                render_table_cell(out, cell, "th", align, refs, opts, bufs);
+                render_table_cell(out, cell, "th", align, refs, opts, bufs, h);
             }
             out.push_str("</tr>\n</thead>\n");
             if !rows.is_empty() {
@@ -195,7 +539,15 @@ fn render_one<'a>(
                         out.push_str("<tr>\n");
                         for cell in row.iter() {
                             out.push_str("<td>");
-                            parse_inline_pass(out, cell, refs, opts, bufs);
+                            render_inline_content(
+                                out,
+                                &cell.raw,
+                                &cell.children,
+                                refs,
+                                opts,
+                                bufs,
+                                h,
+                            );
                             out.push_str("</td>\n");
                         }
                         out.push_str("</tr>\n");
@@ -205,7 +557,7 @@ fn render_one<'a>(
                         out.push_str("<tr>\n");
                         for (i, cell) in row.iter().enumerate() {
                             let align = alignments.get(i).copied().unwrap_or(TableAlignment::None);
-                            render_table_cell(out, cell, "td", align, refs, opts, bufs);
+                            render_table_cell(out, cell, "td", align, refs, opts, bufs, h);
                         }
                         out.push_str("</tr>\n");
                     }
@@ -218,7 +570,7 @@ fn render_one<'a>(
 }
 
 /// Returns `true` if the string contains no characters requiring HTML escaping
-/// or inline parsing (no `<`, `>`, `&`, `"`, or control chars).
+/// or inline parsing (no `<`, `>`, `&`, `"`, markup punctuation or control chars).
 #[inline(always)]
 fn is_trivially_plain(s: &str) -> bool {
     let bytes = s.as_bytes();
@@ -229,8 +581,27 @@ fn is_trivially_plain(s: &str) -> bool {
     if memchr::memchr(b'"', bytes).is_some() {
         return false;
     }
-    // Check for control characters (bytes < 0x20).
-    bytes.iter().all(|&b| b >= b' ')
+    // Check for control characters (bytes < 0x20) and markup punctuation.
+    bytes.iter().all(|&b| {
+        b >= b' '
+            && !matches!(
+                b,
+                b'\\'
+                    | b'`'
+                    | b'*'
+                    | b'_'
+                    | b'['
+                    | b']'
+                    | b'!'
+                    | b'~'
+                    | b'^'
+                    | b'='
+                    | b'+'
+                    | b':'
+                    | b'@'
+                    | b'$'
+            )
+    })
 }
 
 /// For trivially plain text, push directly; otherwise run the full inline pass.
@@ -238,14 +609,25 @@ fn is_trivially_plain(s: &str) -> bool {
 fn push_inline_or_plain(
     out: &mut String,
     raw: &str,
+    children: &[Inline],
     refs: &LinkRefMap,
     opts: &ParseOptions,
     bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
 ) {
-    if is_trivially_plain(raw) {
+    if !children.is_empty() {
+        render_inlines(
+            out,
+            children,
+            opts,
+            &mut bufs.footnotes,
+            &mut bufs.open_tags,
+            h,
+        );
+    } else if !opts.smart_punctuation && is_trivially_plain(raw) {
         out.push_str(raw);
     } else {
-        parse_inline_pass(out, raw, refs, opts, bufs);
+        parse_inline_pass(out, raw, refs, opts, bufs, h);
     }
 }
 
@@ -256,13 +638,16 @@ struct InlineCtx<'a> {
 }
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 fn render_nested_tight_list<'a>(
     kind: &ListKind,
     start: u32,
+    pos: Option<SourcePos>,
     children: &'a [Block],
     inline: InlineCtx<'_>,
     out: &mut String,
     bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
     stack: &mut Vec<Work<'a>>,
 ) {
     const MAX_DEPTH: usize = 64;
@@ -271,14 +656,16 @@ fn render_nested_tight_list<'a>(
 
     let mut cur_kind = kind;
     let mut cur_start = start;
+    let mut cur_pos = pos;
     let mut cur_children: &'a [Block] = children;
 
     loop {
-        emit_list_open(out, cur_kind, cur_start);
+        emit_list_open(out, cur_kind, cur_start, cur_pos, inline.opts);
 
         let Block::ListItem {
             children: item_children,
             checked,
+            pos: item_pos,
         } = &cur_children[0]
         else {
             stack.push(Work::CloseTag(list_close_tag(cur_kind)));
@@ -286,36 +673,39 @@ fn render_nested_tight_list<'a>(
             break;
         };
 
-        out.push_str("<li>");
-        emit_checkbox(out, *checked);
+        open_tag(out, "<li", *item_pos, inline.opts);
+        out.push('>');
+        emit_checkbox(out, *checked, h);
 
         if item_children.len() == 2
             && depth < MAX_DEPTH
             && let (
-                Block::Paragraph { raw },
+                Block::Paragraph { raw, children, .. },
                 Block::List {
                     kind: inner_kind,
                     start: inner_start,
                     tight: true,
                     children: inner_children,
+                    pos: inner_pos,
                 },
             ) = (&item_children[0], &item_children[1])
             && inner_children.len() == 1
         {
-            push_inline_or_plain(out, raw, inline.refs, inline.opts, bufs);
+            push_inline_or_plain(out, raw, children, inline.refs, inline.opts, bufs, h);
             out.push('\n');
             close_tags[depth] = list_close_tag(cur_kind);
             depth += 1;
             cur_kind = inner_kind;
             cur_start = *inner_start;
+            cur_pos = *inner_pos;
             cur_children = inner_children;
             continue;
         }
 
         if item_children.len() == 1
-            && let Block::Paragraph { raw } = &item_children[0]
+            && let Block::Paragraph { raw, children, .. } = &item_children[0]
         {
-            push_inline_or_plain(out, raw, inline.refs, inline.opts, bufs);
+            push_inline_or_plain(out, raw, children, inline.refs, inline.opts, bufs, h);
             // Reserve for unwind: "</li>\n" (6) + close tag (~6) per level
             out.reserve((depth + 1) * 12);
             out.push_str("</li>\n");
@@ -343,8 +733,8 @@ fn render_nested_tight_list<'a>(
         let mut prev_was_para = false;
         for (idx, child) in item_children.iter().enumerate() {
             match child {
-                Block::Paragraph { raw } => {
-                    parse_inline_pass(out, raw, inline.refs, inline.opts, bufs);
+                Block::Paragraph { raw, children, .. } => {
+                    render_inline_content(out, raw, children, inline.refs, inline.opts, bufs, h);
                     prev_was_para = true;
                 }
                 _ => {
@@ -377,30 +767,48 @@ fn render_tight_list_item<'a>(
     out: &mut String,
     opts: &ParseOptions,
     bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
     stack: &mut Vec<Work<'a>>,
 ) {
-    let Block::ListItem { children, checked } = block else {
-        render_one(block, refs, out, opts, bufs, stack);
-        return;
+    let (tag, close, children, checked, pos) = match block {
+        Block::ListItem {
+            children,
+            checked,
+            pos,
+        } => ("<li", "</li>\n", children, *checked, *pos),
+        Block::DefinitionDetails { children, pos } => ("<dd", "</dd>\n", children, None, *pos),
+        _ => {
+            render_one(block, refs, out, opts, bufs, h, stack);
+            return;
+        }
     };
 
-    out.push_str("<li>");
-    emit_checkbox(out, *checked);
+    open_tag(out, tag, pos, opts);
+    out.push('>');
+    emit_checkbox(out, checked, h);
 
     if children.len() == 1
-        && let Block::Paragraph { raw } = &children[0]
+        && let Block::Paragraph {
+            raw,
+            children: inlines,
+            ..
+        } = &children[0]
     {
-        parse_inline_pass(out, raw, refs, opts, bufs);
-        out.push_str("</li>\n");
+        render_inline_content(out, raw, inlines, refs, opts, bufs, h);
+        out.push_str(close);
         return;
     }
 
-    stack.push(Work::CloseTag("</li>\n"));
+    stack.push(Work::CloseTag(close));
     let mut prev_was_para = false;
     for (idx, child) in children.iter().enumerate() {
         match child {
-            Block::Paragraph { raw } => {
-                parse_inline_pass(out, raw, refs, opts, bufs);
+            Block::Paragraph {
+                raw,
+                children: inlines,
+                ..
+            } => {
+                render_inline_content(out, raw, inlines, refs, opts, bufs, h);
                 prev_was_para = true;
             }
             _ => {
@@ -417,14 +825,16 @@ fn render_tight_list_item<'a>(
 }
 
 #[inline]
+#[allow(clippy::too_many_arguments)]
 fn render_table_cell(
     out: &mut String,
-    content: &str,
+    cell: &TableCell,
     tag: &str,
     align: TableAlignment,
     refs: &LinkRefMap,
     opts: &ParseOptions,
     bufs: &mut InlineBuffers,
+    h: &mut dyn HtmlHandler,
 ) {
     out.push('<');
     out.push_str(tag);
@@ -435,7 +845,7 @@ fn render_table_cell(
         TableAlignment::None => {}
     }
     out.push('>');
-    parse_inline_pass(out, content, refs, opts, bufs);
+    render_inline_content(out, &cell.raw, &cell.children, refs, opts, bufs, h);
     out.push_str("</");
     out.push_str(tag);
     out.push_str(">\n");
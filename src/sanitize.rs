@@ -0,0 +1,491 @@
+use crate::ParseOptions;
+use crate::block::resolve_entity_in_bytes;
+use crate::html::{escape_html_into, is_allowed_url};
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+/// Tags and attributes kept by [`RawHtmlMode::Sanitize`](crate::RawHtmlMode::Sanitize).
+///
+/// [`HtmlAllowlist::default`] allows common formatting and structure tags
+/// (`<details>`, `<summary>`, `<kbd>`, `<sup>`, `<sub>`, `<img src>`, tables,
+/// …) with a few harmless attributes. Whatever the allowlist says, event
+/// handler (`on*`) and `style` attributes are always removed, and URL
+/// attributes such as `href` and `src` must use a scheme from
+/// [`allowed_url_schemes`](crate::ParseOptions::allowed_url_schemes). Close
+/// tags without a matching open tag are dropped, and elements left open are
+/// closed at the end of the document.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse, HtmlAllowlist, ParseOptions, RawHtmlMode};
+///
+/// let mut allowlist = HtmlAllowlist::default();
+/// allowlist.allow("video", &["src", "controls"]);
+/// let opts = ParseOptions {
+///     raw_html: RawHtmlMode::Sanitize,
+///     html_allowlist: Some(allowlist),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     parse("<video src=\"a.mp4\" controls onplay=\"x()\"></video>", &opts),
+///     "<p><video src=\"a.mp4\" controls></video></p>\n"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlAllowlist {
+    tags: FxHashMap<String, Vec<String>>,
+}
+
+impl HtmlAllowlist {
+    /// An allowlist that removes every tag.
+    pub fn new() -> Self {
+        Self {
+            tags: FxHashMap::default(),
+        }
+    }
+
+    /// Allows `tag` with the given attributes, replacing any previous entry.
+    /// Names are case-insensitive.
+    pub fn allow(&mut self, tag: &str, attributes: &[&str]) {
+        self.tags.insert(
+            tag.to_ascii_lowercase(),
+            attributes.iter().map(|a| a.to_ascii_lowercase()).collect(),
+        );
+    }
+
+    /// Removes `tag` from the allowlist.
+    pub fn remove(&mut self, tag: &str) {
+        self.tags.remove(&tag.to_ascii_lowercase());
+    }
+
+    /// Attributes allowed on `tag` (lowercase), or `None` if the tag is not allowed.
+    fn attributes(&self, tag: &str) -> Option<&[String]> {
+        self.tags.get(tag).map(Vec::as_slice)
+    }
+}
+
+impl Default for HtmlAllowlist {
+    fn default() -> Self {
+        let mut list = Self::new();
+        for tag in [
+            "b",
+            "br",
+            "caption",
+            "code",
+            "dd",
+            "dfn",
+            "div",
+            "dl",
+            "dt",
+            "em",
+            "figcaption",
+            "figure",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "hr",
+            "i",
+            "kbd",
+            "li",
+            "mark",
+            "p",
+            "pre",
+            "rp",
+            "rt",
+            "ruby",
+            "s",
+            "samp",
+            "small",
+            "span",
+            "strike",
+            "strong",
+            "sub",
+            "summary",
+            "sup",
+            "table",
+            "tbody",
+            "tfoot",
+            "thead",
+            "tr",
+            "tt",
+            "u",
+            "ul",
+            "var",
+        ] {
+            list.allow(tag, &[]);
+        }
+        list.allow("a", &["href", "title"]);
+        list.allow("abbr", &["title"]);
+        list.allow("blockquote", &["cite"]);
+        list.allow("del", &["cite"]);
+        list.allow("details", &["open"]);
+        list.allow("img", &["src", "alt", "title", "width", "height"]);
+        list.allow("ins", &["cite"]);
+        list.allow("ol", &["start"]);
+        list.allow("q", &["cite"]);
+        list.allow("td", &["align", "colspan", "rowspan"]);
+        list.allow("th", &["align", "colspan", "rowspan"]);
+        list
+    }
+}
+
+/// Elements whose content is dropped along with the tag when they are not allowed.
+const RAW_TEXT_TAGS: [&str; 10] = [
+    "script",
+    "style",
+    "textarea",
+    "title",
+    "xmp",
+    "iframe",
+    "noembed",
+    "noframes",
+    "noscript",
+    "plaintext",
+];
+
+/// Elements without a closing tag, never tracked as open.
+const VOID_TAGS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Attributes whose value is a URL and goes through scheme filtering.
+const URL_ATTRIBUTES: [&str; 9] = [
+    "href",
+    "src",
+    "srcset",
+    "cite",
+    "action",
+    "formaction",
+    "poster",
+    "background",
+    "longdesc",
+];
+
+/// Whether `attr="value"` passes scheme filtering: non-URL attributes always
+/// do, and every candidate URL of a `srcset` must be allowed.
+pub(crate) fn is_allowed_url_attribute(
+    attr: &str,
+    value: &str,
+    is_image: bool,
+    allowed: &[String],
+) -> bool {
+    if !URL_ATTRIBUTES.iter().any(|a| a.eq_ignore_ascii_case(attr)) {
+        return true;
+    }
+    if attr.eq_ignore_ascii_case("srcset") {
+        return value.split(',').all(|candidate| {
+            let url = candidate.split_whitespace().next().unwrap_or("");
+            is_allowed_url(url, is_image, allowed)
+        });
+    }
+    is_allowed_url(value, is_image, allowed)
+}
+
+/// Allowed elements opened by sanitized raw HTML and not closed yet. Raw HTML
+/// arrives in pieces (an HTML block, a single inline tag), so this is kept for
+/// the whole document; a close tag with no matching open one is dropped
+/// rather than allowed to end an element of the surrounding page.
+#[derive(Default)]
+pub(crate) struct OpenTags(Vec<String>);
+
+impl OpenTags {
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Forgets elements opened after the first `len`, whose markup was
+    /// dropped from the output.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    /// Closes the elements raw HTML left open, innermost first.
+    pub(crate) fn close_all(&mut self, out: &mut String) {
+        while let Some(name) = self.0.pop() {
+            push_close_tag(out, &name);
+        }
+    }
+}
+
+enum Tag<'a> {
+    Open {
+        name: String,
+        attrs: Vec<(&'a str, Option<&'a str>)>,
+        self_closing: bool,
+    },
+    Close(String),
+    /// Comments, processing instructions, declarations and CDATA.
+    Other,
+}
+
+/// Writes `html` keeping only allowlisted tags and attributes. Text between
+/// tags is kept, with any `<` that does not start a tag escaped.
+pub(crate) fn sanitize_html_into(
+    out: &mut String,
+    html: &str,
+    opts: &ParseOptions,
+    open: &mut OpenTags,
+) {
+    static DEFAULT: OnceLock<HtmlAllowlist> = OnceLock::new();
+    let allowlist = match &opts.html_allowlist {
+        Some(list) => list,
+        None => DEFAULT.get_or_init(HtmlAllowlist::default),
+    };
+    let bytes = html.as_bytes();
+    let mut text_start = 0;
+    let mut i = 0;
+    let mut dropping: Option<String> = None;
+
+    while let Some(off) = memchr::memchr(b'<', &bytes[i..]) {
+        let lt = i + off;
+        let Some((tag, end)) = parse_tag(html, lt) else {
+            i = lt + 1;
+            continue;
+        };
+        if dropping.is_none() {
+            push_text(out, &html[text_start..lt]);
+        }
+        i = end;
+        text_start = end;
+        match tag {
+            Tag::Open {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                if dropping.is_some() {
+                    continue;
+                }
+                if let Some(allowed) = allowlist.attributes(&name) {
+                    push_open_tag(out, &name, &attrs, self_closing, allowed, opts);
+                    if !self_closing && !VOID_TAGS.contains(&name.as_str()) {
+                        open.0.push(name);
+                    }
+                } else if !self_closing && RAW_TEXT_TAGS.contains(&name.as_str()) {
+                    dropping = Some(name);
+                }
+            }
+            Tag::Close(name) => match &dropping {
+                Some(open) if *open == name => dropping = None,
+                Some(_) => {}
+                None => {
+                    // Also closes whatever was opened inside it.
+                    if allowlist.attributes(&name).is_some()
+                        && let Some(i) = open.0.iter().rposition(|tag| *tag == name)
+                    {
+                        for tag in open.0.drain(i..).rev() {
+                            push_close_tag(out, &tag);
+                        }
+                    }
+                }
+            },
+            Tag::Other => {}
+        }
+    }
+    if dropping.is_none() {
+        push_text(out, &html[text_start..]);
+    }
+}
+
+fn push_close_tag(out: &mut String, name: &str) {
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+fn push_text(out: &mut String, text: &str) {
+    let mut last = 0;
+    for (i, _) in text.match_indices('<') {
+        out.push_str(&text[last..i]);
+        out.push_str("&lt;");
+        last = i + 1;
+    }
+    out.push_str(&text[last..]);
+}
+
+fn push_open_tag(
+    out: &mut String,
+    name: &str,
+    attrs: &[(&str, Option<&str>)],
+    self_closing: bool,
+    allowed: &[String],
+    opts: &ParseOptions,
+) {
+    out.push('<');
+    out.push_str(name);
+    for &(attr, value) in attrs {
+        let attr = attr.to_ascii_lowercase();
+        if attr.starts_with("on") || attr == "style" || !allowed.contains(&attr) {
+            continue;
+        }
+        let value = value.map(decode_entities);
+        if !is_allowed_url_attribute(
+            &attr,
+            value.as_deref().unwrap_or(""),
+            name == "img",
+            &opts.allowed_url_schemes,
+        ) {
+            continue;
+        }
+        out.push(' ');
+        out.push_str(&attr);
+        if let Some(value) = value {
+            out.push_str("=\"");
+            escape_html_into(out, &value);
+            out.push('"');
+        }
+    }
+    out.push_str(if self_closing { " />" } else { ">" });
+}
+
+fn decode_entities(s: &str) -> Cow<'_, str> {
+    let bytes = s.as_bytes();
+    if memchr::memchr(b'&', bytes).is_none() {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut last = 0;
+    let mut i = 0;
+    while let Some(off) = memchr::memchr(b'&', &bytes[i..]) {
+        let amp = i + off;
+        out.push_str(&s[last..amp]);
+        match resolve_entity_in_bytes(bytes, amp, &mut out) {
+            Some(end) => i = end,
+            None => {
+                out.push('&');
+                i = amp + 1;
+            }
+        }
+        last = i;
+    }
+    out.push_str(&s[last..]);
+    Cow::Owned(out)
+}
+
+/// Parses the tag starting at `html[lt] == '<'` using CommonMark's raw HTML
+/// grammar. Returns the tag and the offset just past it.
+fn parse_tag(html: &str, lt: usize) -> Option<(Tag<'_>, usize)> {
+    let bytes = html.as_bytes();
+    let rest = &html[lt..];
+    if let Some(end) = other_tag_end(rest) {
+        return Some((Tag::Other, lt + end));
+    }
+    let mut i = lt + 1;
+    let closing = bytes.get(i) == Some(&b'/');
+    if closing {
+        i += 1;
+    }
+    let name_start = i;
+    if !bytes.get(i)?.is_ascii_alphabetic() {
+        return None;
+    }
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+        i += 1;
+    }
+    let name = html[name_start..i].to_ascii_lowercase();
+    if closing {
+        i = skip_whitespace(bytes, i);
+        return (bytes.get(i) == Some(&b'>')).then(|| (Tag::Close(name), i + 1));
+    }
+
+    let mut attrs = Vec::new();
+    loop {
+        let ws_end = skip_whitespace(bytes, i);
+        match bytes.get(ws_end)? {
+            b'>' => {
+                let tag = Tag::Open {
+                    name,
+                    attrs,
+                    self_closing: false,
+                };
+                return Some((tag, ws_end + 1));
+            }
+            b'/' if bytes.get(ws_end + 1) == Some(&b'>') => {
+                let tag = Tag::Open {
+                    name,
+                    attrs,
+                    self_closing: true,
+                };
+                return Some((tag, ws_end + 2));
+            }
+            _ => {}
+        }
+        if ws_end == i {
+            return None;
+        }
+        i = ws_end;
+        let attr_start = i;
+        let first = bytes[i];
+        if !(first.is_ascii_alphabetic() || first == b'_' || first == b':') {
+            return None;
+        }
+        while i < bytes.len()
+            && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'.' | b':' | b'-'))
+        {
+            i += 1;
+        }
+        let attr = &html[attr_start..i];
+        let eq = skip_whitespace(bytes, i);
+        if bytes.get(eq) != Some(&b'=') {
+            attrs.push((attr, None));
+            continue;
+        }
+        let value_start = skip_whitespace(bytes, eq + 1);
+        let (value, end) = match *bytes.get(value_start)? {
+            quote @ (b'"' | b'\'') => {
+                let close = memchr::memchr(quote, &bytes[value_start + 1..])?;
+                let end = value_start + 1 + close;
+                (&html[value_start + 1..end], end + 1)
+            }
+            _ => {
+                let mut end = value_start;
+                while end < bytes.len()
+                    && !bytes[end].is_ascii_whitespace()
+                    && !matches!(bytes[end], b'"' | b'\'' | b'=' | b'<' | b'>' | b'`')
+                {
+                    end += 1;
+                }
+                if end == value_start {
+                    return None;
+                }
+                (&html[value_start..end], end)
+            }
+        };
+        attrs.push((attr, Some(value)));
+        i = end;
+    }
+}
+
+/// Length of a comment, processing instruction, declaration or CDATA section
+/// at the start of `s`. Unterminated ones run to the end of `s`.
+fn other_tag_end(s: &str) -> Option<usize> {
+    let (open, close) = if s.starts_with("<!--") {
+        ("<!--", "-->")
+    } else if s.starts_with("<?") {
+        ("<?", "?>")
+    } else if s.starts_with("<![CDATA[") {
+        ("<![CDATA[", "]]>")
+    } else if s.len() > 2 && s.starts_with("<!") && s.as_bytes()[2].is_ascii_alphabetic() {
+        ("<!", ">")
+    } else {
+        return None;
+    };
+    Some(
+        s[open.len()..]
+            .find(close)
+            .map_or(s.len(), |i| open.len() + i + close.len()),
+    )
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
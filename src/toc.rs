@@ -0,0 +1,169 @@
+use crate::ParseOptions;
+use crate::ast::Block;
+use crate::html::{Slugger, encode_url_escaped_into, escape_html_into};
+use crate::inline::{InlineBuffers, LinkRefMap, inline_plain_text, parse_inlines};
+
+/// One heading in a table of contents, with the headings nested below it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TocEntry {
+    pub level: u8,
+    /// Plain text of the heading, without markup.
+    pub text: String,
+    /// The heading's explicit `{#id}` or `id`, or a slug generated the same
+    /// way as [`ParseOptions::heading_ids`] when it has neither.
+    pub id: String,
+    pub children: Vec<TocEntry>,
+}
+
+/// Collects the headings of a block tree into a nested table of contents.
+///
+/// A heading becomes a child of the closest preceding heading with a lower
+/// level; skipped levels do not add empty entries. Headings with nothing to
+/// link to, such as a bare `#`, are left out.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse_to_ast, toc, ParseOptions};
+///
+/// let ast = parse_to_ast("# Guide\n\n## Install\n\n## Usage", &ParseOptions::default());
+/// let entries = toc(&ast);
+/// assert_eq!(entries[0].text, "Guide");
+/// assert_eq!(entries[0].children[1].id, "usage");
+/// ```
+pub fn toc(block: &Block) -> Vec<TocEntry> {
+    toc_with_refs(block, &LinkRefMap::default(), &ParseOptions::default())
+}
+
+/// Renders a table of contents as nested `<ul>` lists of links to the
+/// heading ids.
+///
+/// # Examples
+///
+/// ```
+/// use ironmark::{parse_to_ast, render_toc, toc, ParseOptions};
+///
+/// let ast = parse_to_ast("# A\n\n## B", &ParseOptions::default());
+/// assert_eq!(
+///     render_toc(&toc(&ast)),
+///     "<ul>\n<li><a href=\"#a\">A</a>\n<ul>\n<li><a href=\"#b\">B</a></li>\n</ul>\n</li>\n</ul>\n"
+/// );
+/// ```
+pub fn render_toc(entries: &[TocEntry]) -> String {
+    let mut out = String::new();
+    render_entries(&mut out, entries);
+    out
+}
+
+fn render_entries(out: &mut String, entries: &[TocEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+    out.push_str("<ul>\n");
+    for entry in entries {
+        out.push_str("<li><a href=\"#");
+        encode_url_escaped_into(out, &entry.id);
+        out.push_str("\">");
+        escape_html_into(out, &entry.text);
+        out.push_str("</a>");
+        if !entry.children.is_empty() {
+            out.push('\n');
+            render_entries(out, &entry.children);
+        }
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n");
+}
+
+/// Like [`toc`], parsing headings without inline `children` from `raw`
+/// against `refs`.
+pub(crate) fn toc_with_refs(
+    block: &Block,
+    refs: &LinkRefMap,
+    opts: &ParseOptions,
+) -> Vec<TocEntry> {
+    let mut slugger = Slugger::default();
+    let mut bufs = InlineBuffers::new();
+    if opts.enable_footnotes {
+        bufs.footnotes.collect(block);
+    }
+    let mut headings: Vec<&Block> = Vec::new();
+    let mut stack: Vec<&Block> = vec![block];
+
+    while let Some(block) = stack.pop() {
+        match block {
+            Block::Document { children, .. }
+            | Block::BlockQuote { children, .. }
+            | Block::Alert { children, .. }
+            | Block::Container { children, .. }
+            | Block::DefinitionList { children, .. }
+            | Block::DefinitionDetails { children, .. }
+            | Block::List { children, .. }
+            | Block::ListItem { children, .. } => stack.extend(children.iter().rev()),
+            Block::Heading { id, attrs, .. } => {
+                if let Some(id) = attrs.get("id").or(id.as_ref()) {
+                    slugger.reserve(id);
+                }
+                headings.push(block);
+            }
+            _ => {}
+        }
+    }
+
+    let mut roots: Vec<TocEntry> = Vec::new();
+    let mut open: Vec<TocEntry> = Vec::new();
+    for heading in headings {
+        let Block::Heading {
+            level,
+            raw,
+            children,
+            id,
+            attrs,
+            ..
+        } = heading
+        else {
+            continue;
+        };
+        let text = if children.is_empty() {
+            inline_plain_text(&parse_inlines(raw, refs, opts, &mut bufs))
+        } else {
+            inline_plain_text(children)
+        };
+        let id = match attrs.get("id").or(id.as_ref()) {
+            Some(id) => slugger.claim(id),
+            None => slugger.slug(&text),
+        };
+        // Nothing to show or link to, as for a bare `#`.
+        if text.trim().is_empty() || id.is_empty() {
+            continue;
+        }
+        while open.last().is_some_and(|top| top.level >= *level) {
+            close_entry(&mut open, &mut roots);
+        }
+        open.push(TocEntry {
+            level: *level,
+            text,
+            id,
+            children: Vec::new(),
+        });
+    }
+    while !open.is_empty() {
+        close_entry(&mut open, &mut roots);
+    }
+    roots
+}
+
+fn close_entry(open: &mut Vec<TocEntry>, roots: &mut Vec<TocEntry>) {
+    let entry = open.pop().unwrap();
+    match open.last_mut() {
+        Some(parent) => parent.children.push(entry),
+        None => roots.push(entry),
+    }
+}
+
+/// Whether `raw` paragraph text is a `[[toc]]` / `[TOC]` placeholder.
+pub(crate) fn is_toc_placeholder(raw: &str) -> bool {
+    let raw = raw.trim();
+    raw.eq_ignore_ascii_case("[[toc]]") || raw.eq_ignore_ascii_case("[toc]")
+}
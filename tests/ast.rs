@@ -0,0 +1,759 @@
+use ironmark::{
+    AlertKind, Attributes, Block, FrontMatterKind, Inline, LinkReferences, ParseOptions, TocEntry,
+    parse, parse_to_ast, parse_to_ast_with_front_matter, parse_to_ast_with_refs, render_html,
+    render_html_with_refs, render_toc, to_markdown, toc,
+};
+
+fn top_level(md: &str) -> Vec<Block> {
+    match parse_to_ast(md, &ParseOptions::default()) {
+        Block::Document { children, .. } => children,
+        other => panic!("expected Document, got {other:?}"),
+    }
+}
+
+fn paragraph_inlines(md: &str) -> Vec<Inline> {
+    match top_level(md).into_iter().next() {
+        Some(Block::Paragraph { children, .. }) => children,
+        other => panic!("expected Paragraph, got {other:?}"),
+    }
+}
+
+fn text(s: &str) -> Inline {
+    Inline::Text {
+        literal: s.to_string(),
+    }
+}
+
+#[test]
+fn paragraph_keeps_raw_and_inlines() {
+    let blocks = top_level("hello *world*");
+    let Block::Paragraph { raw, children, .. } = &blocks[0] else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(raw, "hello *world*");
+    assert_eq!(
+        children,
+        &vec![
+            text("hello "),
+            Inline::Emphasis {
+                children: vec![text("world")],
+            },
+        ]
+    );
+}
+
+#[test]
+fn nested_emphasis_and_extensions() {
+    assert_eq!(
+        paragraph_inlines("**a *b*** ~~c~~ ==d== ++e++"),
+        vec![
+            Inline::Strong {
+                children: vec![
+                    text("a "),
+                    Inline::Emphasis {
+                        children: vec![text("b")],
+                    },
+                ],
+            },
+            text(" "),
+            Inline::Strikethrough {
+                children: vec![text("c")],
+            },
+            text(" "),
+            Inline::Highlight {
+                children: vec![text("d")],
+            },
+            text(" "),
+            Inline::Underline {
+                children: vec![text("e")],
+            },
+        ]
+    );
+}
+
+#[test]
+fn code_spans_and_escapes_are_unescaped_text() {
+    assert_eq!(
+        paragraph_inlines("`a < b` \\* &amp; &lt;"),
+        vec![
+            Inline::Code {
+                literal: "a < b".to_string(),
+            },
+            text(" * & <"),
+        ]
+    );
+}
+
+#[test]
+fn reference_links_are_resolved() {
+    assert_eq!(
+        paragraph_inlines("[**x**][id]\n\n[id]: https://example.com \"T\""),
+        vec![Inline::Link {
+            dest: "https://example.com".to_string(),
+            title: Some("T".to_string()),
+            children: vec![Inline::Strong {
+                children: vec![text("x")],
+            }],
+        }]
+    );
+}
+
+#[test]
+fn images_autolinks_html_and_breaks() {
+    assert_eq!(
+        paragraph_inlines("![alt](a.png)\n<b>x</b>  \n<https://a.b> user@example.com"),
+        vec![
+            Inline::Image {
+                dest: "a.png".to_string(),
+                title: None,
+                children: vec![text("alt")],
+                attrs: Default::default(),
+            },
+            Inline::SoftBreak,
+            Inline::RawHtml {
+                literal: "<b>".to_string(),
+            },
+            text("x"),
+            Inline::RawHtml {
+                literal: "</b>".to_string(),
+            },
+            Inline::HardBreak,
+            Inline::Autolink {
+                url: "https://a.b".to_string(),
+                is_email: false,
+            },
+            text(" "),
+            Inline::Autolink {
+                url: "user@example.com".to_string(),
+                is_email: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn unmatched_delimiters_stay_text() {
+    assert_eq!(paragraph_inlines("**a [b"), vec![text("**a [b")]);
+}
+
+#[test]
+fn headings_and_table_cells_have_inlines() {
+    let blocks = top_level("# *h*\n\n| a | `b` |\n|---|---|\n| **c** | d |");
+    let Block::Heading { children, .. } = &blocks[0] else {
+        panic!("expected Heading");
+    };
+    assert_eq!(
+        children,
+        &vec![Inline::Emphasis {
+            children: vec![text("h")],
+        }]
+    );
+    let Block::Table(td) = &blocks[1] else {
+        panic!("expected Table");
+    };
+    assert_eq!(td.header[0].raw, "a");
+    assert_eq!(
+        td.header[1].children,
+        vec![Inline::Code {
+            literal: "b".to_string(),
+        }]
+    );
+    assert_eq!(
+        td.rows[0][0].children,
+        vec![Inline::Strong {
+            children: vec![text("c")],
+        }]
+    );
+}
+
+#[test]
+fn nested_container_inlines_are_resolved() {
+    let blocks = top_level("> - *x*");
+    let Block::BlockQuote { children, .. } = &blocks[0] else {
+        panic!("expected BlockQuote");
+    };
+    let Block::List { children, .. } = &children[0] else {
+        panic!("expected List");
+    };
+    let Block::ListItem { children, .. } = &children[0] else {
+        panic!("expected ListItem");
+    };
+    let Block::Paragraph { children, .. } = &children[0] else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(
+        children,
+        &vec![Inline::Emphasis {
+            children: vec![text("x")],
+        }]
+    );
+}
+
+fn positioned(md: &str) -> Vec<Block> {
+    let opts = ParseOptions {
+        source_positions: true,
+        ..Default::default()
+    };
+    match parse_to_ast(md, &opts) {
+        Block::Document { children, .. } => children,
+        other => panic!("expected Document, got {other:?}"),
+    }
+}
+
+fn span(block: &Block) -> (usize, usize, usize, usize) {
+    let pos = block.pos().expect("position");
+    (
+        pos.start.line,
+        pos.start.column,
+        pos.end.line,
+        pos.end.column,
+    )
+}
+
+#[test]
+fn positions_are_absent_by_default() {
+    assert!(top_level("# a\n\nb").iter().all(|b| b.pos().is_none()));
+}
+
+#[test]
+fn positions_for_leaf_blocks() {
+    let blocks = positioned("# Title\n\npara one\n  line two\n\n```rs\nx\n```\n\n***\nSetext\n===");
+    assert_eq!(span(&blocks[0]), (1, 1, 1, 8));
+    assert_eq!(span(&blocks[1]), (3, 1, 4, 11));
+    assert_eq!(span(&blocks[2]), (6, 1, 8, 4));
+    assert_eq!(span(&blocks[3]), (10, 1, 10, 4));
+    assert_eq!(span(&blocks[4]), (11, 1, 12, 4));
+
+    let pos = blocks[1].pos().unwrap();
+    assert_eq!(pos.start.offset, 9);
+    assert_eq!(pos.end.offset, 28);
+}
+
+#[test]
+fn positions_skip_link_reference_definitions() {
+    let blocks = positioned("[x]: /u\npara\n\n> [y]:\n>   /v\n> quoted\n\n[z]: /w\nSetext\n===");
+    assert_eq!(span(&blocks[0]), (2, 1, 2, 5));
+    assert_eq!(blocks[0].pos().unwrap().start.offset, 8);
+    let Block::BlockQuote { children, .. } = &blocks[1] else {
+        panic!("expected BlockQuote");
+    };
+    assert_eq!(span(&children[0]), (6, 3, 6, 9));
+    assert_eq!(span(&blocks[2]), (9, 1, 10, 4));
+}
+
+#[test]
+fn positions_for_containers() {
+    let blocks = positioned("> quote\nlazy\n\n- a\n- b\n\n  more\n\n| a |\n|---|\n| 1 |");
+    assert_eq!(span(&blocks[0]), (1, 1, 2, 5));
+    assert_eq!(span(&blocks[1]), (4, 1, 7, 7));
+    let Block::List { children, .. } = &blocks[1] else {
+        panic!("expected List");
+    };
+    assert_eq!(span(&children[0]), (4, 1, 4, 4));
+    assert_eq!(span(&children[1]), (5, 1, 7, 7));
+    let Block::ListItem { children, .. } = &children[1] else {
+        panic!("expected ListItem");
+    };
+    assert_eq!(span(&children[1]), (7, 3, 7, 7));
+    assert_eq!(span(&blocks[2]), (9, 1, 11, 6));
+}
+
+#[test]
+fn render_html_matches_parse_with_extensions() {
+    let md = "# T *x*\n\n- [x] ==a== ~~b~~ ++c++\n- https://a.b\n\n| h | `i` |\n|:-|-:|\n| **j** | ![k *l*](m \"n\") |\n\nline\nbreak [r]\n\n[r]: /u";
+    let opts = ParseOptions::default();
+    assert_eq!(
+        render_html(&parse_to_ast(md, &opts), &opts),
+        parse(md, &opts)
+    );
+}
+
+#[test]
+fn render_html_reflects_tree_edits() {
+    let opts = ParseOptions::default();
+    let mut doc = parse_to_ast("Hello *world*", &opts);
+    if let Block::Document { children, .. } = &mut doc
+        && let Some(Block::Paragraph { children, .. }) = children.first_mut()
+    {
+        children[0] = text("Bye <");
+        children.push(Inline::Code {
+            literal: "x".to_string(),
+        });
+    }
+    assert_eq!(
+        render_html(&doc, &opts),
+        "<p>Bye &lt;<em>world</em><code>x</code></p>\n"
+    );
+}
+
+#[test]
+fn raw_fallback_resolves_supplied_refs() {
+    let opts = ParseOptions::default();
+    let doc = Block::Document {
+        children: vec![Block::Paragraph {
+            raw: "[Home]".to_string(),
+            children: Vec::new(),
+            pos: None,
+        }],
+        pos: None,
+    };
+    assert_eq!(render_html(&doc, &opts), "<p>[Home]</p>\n");
+
+    let mut refs = LinkReferences::new();
+    refs.insert("home", "/", Some("Start"));
+    assert_eq!(
+        render_html_with_refs(&doc, &refs, &opts),
+        "<p><a href=\"/\" title=\"Start\">Home</a></p>\n"
+    );
+}
+
+#[test]
+fn parse_exposes_link_references() {
+    let (_, refs) = parse_to_ast_with_refs(
+        "[Foo  Bar]: /a&amp;b 'T'\n[foo bar]: /ignored\n",
+        &ParseOptions::default(),
+    );
+    assert_eq!(refs.len(), 1);
+    let def = refs.get("FOO BAR").expect("definition");
+    assert_eq!(def.dest, "/a&b");
+    assert_eq!(def.title, Some("T"));
+}
+
+#[test]
+fn heading_ids_are_exposed() {
+    let opts = ParseOptions {
+        heading_ids: true,
+        ..Default::default()
+    };
+    let ast = parse_to_ast(
+        "# Intro\n\n> ## *Intro*\n\n- ### `a.b` [c][]\n\n[c]: /c",
+        &opts,
+    );
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let mut ids = Vec::new();
+    let mut stack: Vec<&Block> = children.iter().rev().collect();
+    while let Some(block) = stack.pop() {
+        match block {
+            Block::Heading { id, .. } => ids.push(id.clone().unwrap()),
+            Block::BlockQuote { children, .. }
+            | Block::List { children, .. }
+            | Block::ListItem { children, .. } => stack.extend(children.iter().rev()),
+            _ => {}
+        }
+    }
+    assert_eq!(ids, ["intro", "intro-1", "ab-c"]);
+    assert_eq!(
+        render_html(&ast, &opts),
+        parse(
+            "# Intro\n\n> ## *Intro*\n\n- ### `a.b` [c][]\n\n[c]: /c",
+            &opts
+        )
+    );
+}
+
+#[test]
+fn toc_nests_headings_by_level() {
+    let ast = parse_to_ast(
+        "### Early\n\n# *Guide*\n\n### Deep\n\n## Setup\n\n> ## Setup\n\n# API & <b>more</b>",
+        &ParseOptions::default(),
+    );
+    let entry = |level, text: &str, id: &str, children| TocEntry {
+        level,
+        text: text.to_string(),
+        id: id.to_string(),
+        children,
+    };
+    assert_eq!(
+        toc(&ast),
+        vec![
+            entry(3, "Early", "early", vec![]),
+            entry(
+                1,
+                "Guide",
+                "guide",
+                vec![
+                    entry(3, "Deep", "deep", vec![]),
+                    entry(2, "Setup", "setup", vec![]),
+                    entry(2, "Setup", "setup-1", vec![]),
+                ],
+            ),
+            entry(1, "API & more", "api--more", vec![]),
+        ]
+    );
+    assert_eq!(render_toc(&[]), "");
+    assert_eq!(
+        render_toc(&toc(&ast)[2..]),
+        "<ul>\n<li><a href=\"#api--more\">API &amp; more</a></li>\n</ul>\n"
+    );
+}
+
+#[test]
+fn toc_skips_empty_headings() {
+    let ast = parse_to_ast("# A\n\n## !!!\n\n#\n\n# B", &ParseOptions::default());
+    let entries = toc(&ast);
+    let texts: Vec<_> = entries.iter().map(|entry| entry.text.as_str()).collect();
+    assert_eq!(texts, ["A", "B"]);
+    assert!(entries[0].children.is_empty());
+    assert!(!render_toc(&entries).contains("href=\"#\""));
+}
+
+#[test]
+fn footnotes_in_ast() {
+    let opts = ParseOptions {
+        enable_footnotes: true,
+        ..Default::default()
+    };
+    let md = "Text[^n].\n\n[^n]: Note *one*.\n\n    Two.";
+    let ast = parse_to_ast(md, &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::Paragraph {
+        children: inlines, ..
+    } = &children[0]
+    else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(
+        inlines[1],
+        Inline::FootnoteReference {
+            label: "n".to_string()
+        }
+    );
+    let Block::FootnoteDefinition {
+        label,
+        children: body,
+        ..
+    } = &children[1]
+    else {
+        panic!("expected FootnoteDefinition");
+    };
+    assert_eq!(label, "n");
+    assert_eq!(body.len(), 2);
+    assert_eq!(render_html(&ast, &opts), parse(md, &opts));
+    assert_eq!(
+        to_markdown(&ast),
+        "Text[^n].\n\n[^n]: Note *one*.\n\n    Two.\n"
+    );
+}
+
+#[test]
+fn math_in_ast() {
+    let opts = ParseOptions {
+        enable_math: true,
+        ..Default::default()
+    };
+    let md = "Area $\\pi r^2$ and $`a$b`$.\n\n$$\nx^2\n$$";
+    let ast = parse_to_ast(md, &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::Paragraph {
+        children: inlines, ..
+    } = &children[0]
+    else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(
+        inlines[1],
+        Inline::Math {
+            literal: "\\pi r^2".to_string(),
+            display: false,
+        }
+    );
+    assert!(matches!(&children[1], Block::MathBlock { literal, .. } if literal == "x^2"));
+    assert_eq!(render_html(&ast, &opts), parse(md, &opts));
+    let md_out = to_markdown(&ast);
+    assert_eq!(md_out, "Area $\\pi r^2$ and $`a$b`$.\n\n$$\nx^2\n$$\n");
+    assert_eq!(parse_to_ast(&md_out, &opts), ast);
+}
+
+#[test]
+fn alerts_in_ast() {
+    let opts = ParseOptions {
+        enable_alerts: true,
+        source_positions: true,
+        ..Default::default()
+    };
+    let md = "> [!Caution]\n> Mind the *gap*.";
+    let ast = parse_to_ast(md, &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::Alert {
+        kind,
+        children: body,
+        ..
+    } = &children[0]
+    else {
+        panic!("expected Alert");
+    };
+    assert_eq!(*kind, AlertKind::Caution);
+    let Block::Paragraph { raw, pos, .. } = &body[0] else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(raw, "Mind the *gap*.");
+    let start = pos.unwrap().start;
+    assert_eq!((start.line, start.column, start.offset), (2, 3, 15));
+    assert_eq!(render_html(&ast, &opts), parse(md, &opts));
+    assert_eq!(to_markdown(&ast), "> [!CAUTION]\n> Mind the *gap*.\n");
+}
+
+#[test]
+fn containers_in_ast() {
+    let opts = ParseOptions {
+        enable_containers: true,
+        source_positions: true,
+        ..Default::default()
+    };
+    let md = ":::: outer a=1\n::: inner\nx\n:::\n::::";
+    let ast = parse_to_ast(md, &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::Container {
+        name,
+        attributes,
+        children: body,
+        pos,
+    } = &children[0]
+    else {
+        panic!("expected Container");
+    };
+    assert_eq!((name.as_str(), attributes.as_str()), ("outer", "a=1"));
+    assert_eq!(pos.unwrap().end.line, 5);
+    assert!(matches!(&body[0], Block::Container { name, .. } if name == "inner"));
+    assert_eq!(render_html(&ast, &opts), parse(md, &opts));
+    assert_eq!(
+        to_markdown(&ast),
+        ":::: outer a=1\n::: inner\nx\n:::\n::::\n"
+    );
+}
+
+#[test]
+fn definition_lists_in_ast() {
+    let opts = ParseOptions {
+        enable_definition_lists: true,
+        ..Default::default()
+    };
+    let md = "Term *one*\n: First\n: Second\n\nTerm two\n: Third";
+    let ast = parse_to_ast(md, &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::DefinitionList {
+        tight,
+        children: items,
+        ..
+    } = &children[0]
+    else {
+        panic!("expected DefinitionList");
+    };
+    assert!(*tight);
+    let kinds: Vec<&str> = items
+        .iter()
+        .map(|b| match b {
+            Block::DefinitionTerm { .. } => "dt",
+            Block::DefinitionDetails { .. } => "dd",
+            _ => "?",
+        })
+        .collect();
+    assert_eq!(kinds, ["dt", "dd", "dd", "dt", "dd"]);
+    let Block::DefinitionTerm { children: term, .. } = &items[0] else {
+        unreachable!()
+    };
+    assert!(matches!(term[1], Inline::Emphasis { .. }));
+    assert_eq!(render_html(&ast, &opts), parse(md, &opts));
+    assert_eq!(to_markdown(&ast), format!("{md}\n"));
+}
+
+#[test]
+fn front_matter_with_ast() {
+    let opts = ParseOptions {
+        source_positions: true,
+        ..Default::default()
+    };
+    let out = parse_to_ast_with_front_matter("+++\ndraft = true\n+++\n# Title", &opts);
+    let front_matter = out.front_matter.unwrap();
+    assert_eq!(front_matter.kind, FrontMatterKind::Toml);
+    assert_eq!(front_matter.raw, "draft = true\n");
+    let Block::Document { children, .. } = &out.body else {
+        panic!("expected Document");
+    };
+    assert_eq!(children.len(), 1);
+    // Positions still count the front matter lines.
+    let start = children[0].pos().unwrap().start;
+    assert_eq!((start.line, start.offset), (4, 21));
+}
+
+#[test]
+fn attributes_in_ast() {
+    let opts = ParseOptions {
+        enable_attributes: true,
+        ..Default::default()
+    };
+    let ast = parse_to_ast(
+        "# Intro {#start .lead}\n\n```{.sh}\nls\n```\n\n![a](b.png){#pic}",
+        &opts,
+    );
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let attrs = |pairs: &[(&str, &str)]| -> Attributes {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+    let Block::Heading {
+        raw,
+        id,
+        attrs: heading,
+        ..
+    } = &children[0]
+    else {
+        panic!("expected Heading");
+    };
+    assert_eq!(raw, "Intro");
+    assert_eq!(*id, None);
+    assert_eq!(*heading, attrs(&[("id", "start"), ("class", "lead")]));
+    let Block::CodeBlock {
+        info, attrs: code, ..
+    } = &children[1]
+    else {
+        panic!("expected CodeBlock");
+    };
+    assert_eq!(info, "sh");
+    assert!(code.is_empty());
+    let Block::Paragraph {
+        children: inlines, ..
+    } = &children[2]
+    else {
+        panic!("expected Paragraph");
+    };
+    let [Inline::Image { attrs: image, .. }] = inlines.as_slice() else {
+        panic!("expected Image, got {inlines:?}");
+    };
+    assert_eq!(*image, attrs(&[("id", "pic")]));
+    assert_eq!(
+        to_markdown(&ast),
+        "# Intro {#start .lead}\n\n```sh\nls\n```\n\n![a](b.png){#pic}\n"
+    );
+}
+
+#[test]
+fn smart_punctuation_in_ast() {
+    let opts = ParseOptions {
+        smart_punctuation: true,
+        ..Default::default()
+    };
+    let Block::Document { children, .. } = parse_to_ast("\"Don't\" -- *'x'*", &opts) else {
+        panic!("expected Document");
+    };
+    let Block::Paragraph { children, .. } = &children[0] else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(
+        *children,
+        vec![
+            Inline::Text {
+                literal: "“Don’t” – ".to_string()
+            },
+            Inline::Emphasis {
+                children: vec![Inline::Text {
+                    literal: "‘x’".to_string()
+                }]
+            },
+        ]
+    );
+}
+
+#[test]
+fn superscript_and_subscript_in_ast() {
+    let opts = ParseOptions {
+        enable_superscript: true,
+        enable_subscript: true,
+        ..Default::default()
+    };
+    let ast = parse_to_ast("H~2~O x^2^", &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::Paragraph { children, .. } = &children[0] else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(
+        *children,
+        vec![
+            text("H"),
+            Inline::Subscript {
+                children: vec![text("2")],
+            },
+            text("O x"),
+            Inline::Superscript {
+                children: vec![text("2")],
+            },
+        ]
+    );
+    assert_eq!(to_markdown(&ast), "H~2~O x^2^\n");
+}
+
+#[test]
+fn toc_keeps_explicit_ids_unique() {
+    let opts = ParseOptions {
+        enable_attributes: true,
+        ..Default::default()
+    };
+    let ast = parse_to_ast(
+        "# Intro {#custom}\n\n## Custom\n\n## Other {#custom}",
+        &opts,
+    );
+    let entries = toc(&ast);
+    let ids: Vec<_> = entries[0]
+        .children
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect();
+    assert_eq!(entries[0].id, "custom");
+    assert_eq!(ids, ["custom-2", "custom-1"]);
+}
+
+#[test]
+fn emoji_in_ast() {
+    let opts = ParseOptions {
+        enable_emoji: true,
+        custom_emoji: Some(Box::new(|name: &str| {
+            (name == "shipit").then(|| "/shipit.png".to_string())
+        })),
+        ..Default::default()
+    };
+    let ast = parse_to_ast("Done :tada: :shipit:", &opts);
+    let Block::Document { children, .. } = &ast else {
+        panic!("expected Document");
+    };
+    let Block::Paragraph { children, .. } = &children[0] else {
+        panic!("expected Paragraph");
+    };
+    assert_eq!(
+        *children,
+        vec![
+            text("Done "),
+            Inline::Emoji {
+                name: "tada".to_string(),
+                literal: "\u{1F389}".to_string(),
+            },
+            text(" "),
+            Inline::CustomEmoji {
+                name: "shipit".to_string(),
+                url: "/shipit.png".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        render_html(&ast, &opts),
+        "<p>Done \u{1F389} <img class=\"emoji\" src=\"/shipit.png\" alt=\":shipit:\" /></p>\n"
+    );
+    assert_eq!(to_markdown(&ast), "Done :tada: :shipit:\n");
+}
@@ -1,4 +1,7 @@
-use ironmark::{ParseOptions, parse};
+use ironmark::{
+    FormatOptions, HeadingStyle, OrderedNumbering, ParseOptions, format, parse, parse_to_ast,
+    parse_to_ast_with_refs, render_html_with_refs, to_markdown,
+};
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
@@ -64,3 +67,111 @@ fn commonmark_0_31_2_spec() {
         panic!("{fail} spec tests failed (see details above)");
     }
 }
+
+#[test]
+fn commonmark_spec_ast_roundtrip() {
+    let spec_json = include_str!("./spec/spec-0.31.2.json");
+    let tests: Vec<SpecTest> = serde_json::from_str(spec_json).expect("Failed to parse spec JSON");
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_autolink: false,
+        ..Default::default()
+    };
+
+    let mut failures = Vec::new();
+    for test in &tests {
+        let (ast, refs) = parse_to_ast_with_refs(&test.markdown, &opts);
+        let rendered = render_html_with_refs(&ast, &refs, &opts);
+        let expected = parse(&test.markdown, &opts);
+        if rendered != expected {
+            failures.push(format!(
+                "example {}\n  input:    {:?}\n  parse:    {:?}\n  rendered: {:?}",
+                test.example, test.markdown, expected, rendered
+            ));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} examples render differently from the AST:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+#[test]
+fn commonmark_spec_markdown_roundtrip() {
+    let spec_json = include_str!("./spec/spec-0.31.2.json");
+    let tests: Vec<SpecTest> = serde_json::from_str(spec_json).expect("Failed to parse spec JSON");
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_autolink: false,
+        ..Default::default()
+    };
+
+    let mut failures = Vec::new();
+    for test in &tests {
+        let markdown = to_markdown(&parse_to_ast(&test.markdown, &opts));
+        let expected = parse(&test.markdown, &opts);
+        let reparsed = parse(&markdown, &opts);
+        if reparsed != expected {
+            failures.push(format!(
+                "example {}\n  input:      {:?}\n  serialized: {:?}\n  expected:   {:?}\n  got:        {:?}",
+                test.example, test.markdown, markdown, expected, reparsed
+            ));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} examples change meaning after to_markdown:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+#[test]
+fn commonmark_spec_format_preserves_meaning() {
+    let spec_json = include_str!("./spec/spec-0.31.2.json");
+    let tests: Vec<SpecTest> = serde_json::from_str(spec_json).expect("Failed to parse spec JSON");
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_autolink: false,
+        ..Default::default()
+    };
+    let style = FormatOptions {
+        bullet: Some(b'*'),
+        ordered_delimiter: Some(b')'),
+        ordered_numbering: OrderedNumbering::Repeat,
+        heading_style: HeadingStyle::Setext,
+        fence: b'~',
+        pad_tables: true,
+        wrap_width: Some(20),
+    };
+    // Re-flowing paragraphs only moves soft breaks, so compare with
+    // whitespace runs collapsed.
+    let collapse = |html: &str| html.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut failures = Vec::new();
+    for test in &tests {
+        let formatted = format(&test.markdown, &opts, &style);
+        let expected = collapse(&parse(&test.markdown, &opts));
+        let reparsed = collapse(&parse(&formatted, &opts));
+        if reparsed != expected || format(&formatted, &opts, &style) != formatted {
+            failures.push(format!(
+                "example {}\n  input:     {:?}\n  formatted: {:?}\n  expected:  {:?}\n  got:       {:?}",
+                test.example, test.markdown, formatted, expected, reparsed
+            ));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} examples change after format:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use ironmark::{
+    Block, FormatOptions, HeadingStyle, Inline, ListKind, OrderedNumbering, ParseOptions, format,
+    parse, parse_to_ast, to_markdown,
+};
+
+fn roundtrip(md: &str) -> String {
+    let opts = ParseOptions::default();
+    let out = to_markdown(&parse_to_ast(md, &opts));
+    assert_eq!(parse(&out, &opts), parse(md, &opts), "serialized: {out:?}");
+    out
+}
+
+#[test]
+fn canonical_block_syntax() {
+    assert_eq!(
+        roundtrip("Title\n=====\n\nSub\n---\n\n    code\n\n___\n\n> quote\nlazy"),
+        "# Title\n\n## Sub\n\n```\ncode\n```\n\n***\n\n> quote\n> lazy\n",
+    );
+}
+
+#[test]
+fn lists_keep_markers_and_task_states() {
+    assert_eq!(
+        roundtrip("+ [ ] a\n+ [x] b\n\n3) c\n4) d\n\n- e\n\n  f"),
+        "+ [ ] a\n+ [x] b\n\n3) c\n4) d\n\n- e\n\n  f\n",
+    );
+}
+
+#[test]
+fn adjacent_lists_of_same_kind_stay_separate() {
+    let item = |s: &str| Block::ListItem {
+        children: vec![Block::Paragraph {
+            raw: s.to_string(),
+            children: Vec::new(),
+            pos: None,
+        }],
+        checked: None,
+        pos: None,
+    };
+    let list = |s: &str| Block::List {
+        kind: ListKind::Bullet(b'-'),
+        start: 1,
+        tight: true,
+        children: vec![item(s)],
+        pos: None,
+    };
+    let doc = Block::Document {
+        children: vec![list("a"), list("b")],
+        pos: None,
+    };
+    assert_eq!(to_markdown(&doc), "- a\n\n* b\n");
+}
+
+#[test]
+fn fences_outgrow_code_content() {
+    assert_eq!(
+        roundtrip("````md\n```\n````\n\n~~~\n`` `x` ``\n~~~\n\n~~~ a`b\nx\n~~~"),
+        "````md\n```\n````\n\n```\n`` `x` ``\n```\n\n~~~a`b\nx\n~~~\n",
+    );
+}
+
+#[test]
+fn tables_keep_alignment() {
+    assert_eq!(
+        roundtrip("a|b|c|d\n-|:-|:-:|-:\n`x\\|y`|**z**||w"),
+        "| a | b | c | d |\n| --- | :-- | :-: | --: |\n| `x\\|y` | **z** |  | w |\n",
+    );
+}
+
+#[test]
+fn inline_syntax_is_escaped_and_nested() {
+    roundtrip("\\*not em\\* 1\\. \\# \\[x\\] a\\_b \\<br> &amp;copy; \\~~no~~");
+    roundtrip("***a*** **b *c*** *d **e*** `` ` `` [l *m*](</a b> \"t\\\"\") ![i](j)");
+    roundtrip("\\- x\n\\+ y\n2\\. z\n\\> q\n\\=\\=\\=");
+    roundtrip("line  \nbreak\\\nagain <span>html</span> <https://a.b> c@d.ef");
+}
+
+#[test]
+fn escaped_shortcodes_stay_literal() {
+    let opts = ParseOptions {
+        enable_emoji: true,
+        ..Default::default()
+    };
+    let md = "\\:tada\\: :tada: a\\:b:c: :x";
+    let out = to_markdown(&parse_to_ast(md, &opts));
+    assert_eq!(parse(&out, &opts), parse(md, &opts), "serialized: {out:?}");
+    assert!(parse(&out, &opts).contains(":tada: 🎉"));
+}
+
+#[test]
+fn edited_inlines_are_serialized() {
+    let mut doc = parse_to_ast("hello", &ParseOptions::default());
+    if let Block::Document { children, .. } = &mut doc
+        && let Some(Block::Paragraph { children, .. }) = children.first_mut()
+    {
+        children.push(Inline::Emphasis {
+            children: vec![Inline::Text {
+                literal: "world*".to_string(),
+            }],
+        });
+    }
+    let md = to_markdown(&doc);
+    assert_eq!(md, "hello*world\\**\n");
+    assert_eq!(
+        parse(&md, &ParseOptions::default()),
+        "<p>hello<em>world*</em></p>\n"
+    );
+}
+
+fn fmt(md: &str, options: &FormatOptions) -> String {
+    fmt_with(md, &ParseOptions::default(), options)
+}
+
+fn fmt_with(md: &str, opts: &ParseOptions, options: &FormatOptions) -> String {
+    let out = format(md, opts, options);
+    assert_eq!(format(&out, opts, options), out, "format is not idempotent");
+    out
+}
+
+#[test]
+fn format_normalizes_list_markers() {
+    let defaults = FormatOptions::default();
+    assert_eq!(
+        fmt("* a\n+ b\n\n3) x\n7) y", &defaults),
+        "- a\n\n* b\n\n3. x\n4. y\n"
+    );
+    let repeat = FormatOptions {
+        bullet: Some(b'*'),
+        ordered_delimiter: Some(b')'),
+        ordered_numbering: OrderedNumbering::Repeat,
+        ..Default::default()
+    };
+    assert_eq!(
+        fmt("- a\n- ***\n\n1. x\n2. y", &repeat),
+        "* a\n* ---\n\n1) x\n1) y\n"
+    );
+}
+
+#[test]
+fn format_heading_and_fence_styles() {
+    let style = FormatOptions {
+        heading_style: HeadingStyle::Setext,
+        fence: b'~',
+        ..Default::default()
+    };
+    assert_eq!(
+        fmt("# Title\n\n## Sub\n\n### Deep\n\n```js\nx\n```", &style),
+        "Title\n=====\n\nSub\n---\n\n### Deep\n\n~~~js\nx\n~~~\n",
+    );
+    assert_eq!(
+        fmt("Title\n===\n\n~~~\nx\n~~~", &FormatOptions::default()),
+        "# Title\n\n```\nx\n```\n",
+    );
+}
+
+#[test]
+fn format_pads_tables_by_alignment() {
+    assert_eq!(
+        fmt(
+            "|a|b|c|\n|-|:-:|-:|\n|long cell|x|1|\n|y|wider|22|",
+            &FormatOptions::default()
+        ),
+        "| a         |   b   |   c |\n\
+         | --------- | :---: | --: |\n\
+         | long cell |   x   |   1 |\n\
+         | y         | wider |  22 |\n",
+    );
+}
+
+#[test]
+fn format_wraps_paragraphs_within_containers() {
+    let soft = ParseOptions {
+        hard_breaks: false,
+        ..Default::default()
+    };
+    let style = FormatOptions {
+        wrap_width: Some(16),
+        ..Default::default()
+    };
+    assert_eq!(
+        fmt_with(
+            "> one two three four five\n\n- alpha beta gamma delta\n  epsilon",
+            &soft,
+            &style
+        ),
+        "> one two three\n> four five\n\n- alpha beta\n  gamma delta\n  epsilon\n",
+    );
+    // Never break before text that would start a list, heading or quote.
+    let narrow = FormatOptions {
+        wrap_width: Some(4),
+        ..Default::default()
+    };
+    assert_eq!(
+        fmt_with("aaaa 1. bb - cc # dd", &soft, &narrow),
+        "aaaa 1.\nbb -\ncc #\ndd\n"
+    );
+}
+
+#[test]
+fn format_wrap_drops_spaces_before_inserted_break() {
+    let soft = ParseOptions {
+        hard_breaks: false,
+        ..Default::default()
+    };
+    let style = FormatOptions {
+        wrap_width: Some(4),
+        ..Default::default()
+    };
+    let out = fmt_with("aaaa    b", &soft, &style);
+    assert_eq!(out, "aaaa\nb\n");
+    assert!(!parse(&out, &soft).contains("<br"));
+}
+
+#[test]
+fn format_does_not_wrap_with_hard_breaks() {
+    let opts = ParseOptions::default();
+    let style = FormatOptions {
+        wrap_width: Some(10),
+        ..Default::default()
+    };
+    let md = "one two three four\nfive";
+    let out = fmt(md, &style);
+    assert_eq!(out, "one two three four\nfive\n");
+    assert_eq!(parse(&out, &opts), parse(md, &opts));
+}
+
+#[test]
+fn format_keeps_front_matter() {
+    let opts = ParseOptions {
+        front_matter: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        format(
+            "+++\ntitle = \"x\"\n+++\n* a",
+            &opts,
+            &FormatOptions::default()
+        ),
+        "+++\ntitle = \"x\"\n+++\n\n- a\n"
+    );
+}
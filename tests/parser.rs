@@ -1,4 +1,8 @@
-use ironmark::{ParseOptions, parse};
+use ironmark::{
+    FrontMatter, FrontMatterKind, HtmlAllowlist, HtmlHandler, ParseError, ParseOptions,
+    RawHtmlMode, parse, parse_to_ast_with_refs, parse_with_front_matter, parse_with_handler,
+    render_html_with_handler, render_html_with_refs, try_parse,
+};
 
 fn assert_html(md: &str, expected: &str) {
     let opts = ParseOptions {
@@ -380,6 +384,46 @@ fn underline_disabled() {
     assert_eq!(parse("++underlined++", &opts), "<p>++underlined++</p>\n");
 }
 
+// ── Superscript and subscript ──────────────────────────────────────
+
+#[test]
+fn parses_superscript_and_subscript() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_superscript: true,
+        enable_subscript: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("H~2~O and x^-1^, **y^3^**", &opts),
+        "<p>H<sub>2</sub>O and x<sup>-1</sup>, <strong>y<sup>3</sup></strong></p>\n"
+    );
+    assert_eq!(
+        parse("~~H~2~O~~ ~~gone~~ a~b~~c~~", &opts),
+        "<p><del>H<sub>2</sub>O</del> <del>gone</del> a~b<del>c</del></p>\n"
+    );
+}
+
+#[test]
+fn script_markers_need_tight_content() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_superscript: true,
+        enable_subscript: true,
+        ..Default::default()
+    };
+    assert_eq!(parse("2^10 and 2^20", &opts), "<p>2^10 and 2^20</p>\n");
+    assert_eq!(
+        parse("~a b~ ^^x^^ H~2~~O", &opts),
+        "<p>~a b~ ^^x^^ H~2~~O</p>\n"
+    );
+}
+
+#[test]
+fn superscript_and_subscript_disabled() {
+    assert_html("H~2~O x^2^", "<p>H~2~O x^2^</p>\n");
+}
+
 // ── Tables toggle ──────────────────────────────────────────────────
 
 #[test]
@@ -611,3 +655,859 @@ fn task_list_loose() {
         "<ul>\n<li><input type=\"checkbox\" disabled=\"\" /> \n<p>item one</p>\n</li>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> \n<p>item two</p>\n</li>\n</ul>\n",
     );
 }
+
+fn sourcepos_html(md: &str) -> String {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        sourcepos: true,
+        ..Default::default()
+    };
+    parse(md, &opts)
+}
+
+#[test]
+fn sourcepos_attributes_on_blocks() {
+    assert_eq!(
+        sourcepos_html("# Title\n\n> quote\n\n```\ncode\n```\n\n---"),
+        "<h1 data-sourcepos=\"1:1-1:7\">Title</h1>\n\
+         <blockquote data-sourcepos=\"3:1-3:7\">\n<p data-sourcepos=\"3:3-3:7\">quote</p>\n</blockquote>\n\
+         <pre data-sourcepos=\"5:1-7:3\"><code>code\n</code></pre>\n\
+         <hr data-sourcepos=\"9:1-9:3\" />\n",
+    );
+}
+
+#[test]
+fn sourcepos_attributes_on_lists_and_tables() {
+    assert_eq!(
+        sourcepos_html("| x |\n|---|\n| 1 |\n\n3. a\n   - b"),
+        "<table data-sourcepos=\"1:1-3:5\">\n<thead>\n<tr>\n<th>x</th>\n</tr>\n</thead>\n\
+         <tbody>\n<tr>\n<td>1</td>\n</tr>\n</tbody>\n</table>\n\
+         <ol data-sourcepos=\"5:1-6:6\" start=\"3\">\n<li data-sourcepos=\"5:1-6:6\">a\n\
+         <ul data-sourcepos=\"6:4-6:6\">\n<li data-sourcepos=\"6:4-6:6\">b</li>\n</ul>\n</li>\n</ol>\n",
+    );
+}
+
+struct Overrides;
+
+fn escape_attr(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl HtmlHandler for Overrides {
+    fn code_block(&mut self, out: &mut String, info: &str, literal: &str) -> bool {
+        if info != "mermaid" {
+            return false;
+        }
+        out.push_str("<div class=\"mermaid\">");
+        out.push_str(literal.trim_end());
+        out.push_str("</div>\n");
+        true
+    }
+
+    fn heading_open(&mut self, out: &mut String, level: u8, raw: &str, _id: Option<&str>) -> bool {
+        out.push_str(&format!(
+            "<h{level} id=\"{}\">",
+            raw.to_lowercase().replace(' ', "-")
+        ));
+        true
+    }
+
+    fn link_open(&mut self, out: &mut String, dest: &str, _title: Option<&str>) -> bool {
+        out.push_str(&format!("<a class=\"ext\" href=\"{}\">", escape_attr(dest)));
+        true
+    }
+
+    fn link_close(&mut self, out: &mut String) -> bool {
+        out.push_str(" &#8599;</a>");
+        true
+    }
+
+    fn image(&mut self, out: &mut String, dest: &str, _title: Option<&str>, alt: &str) -> bool {
+        out.push_str(&format!(
+            "<figure><img src=\"{}\"><figcaption>{}</figcaption></figure>",
+            escape_attr(dest),
+            escape_attr(alt)
+        ));
+        true
+    }
+
+    fn task_checkbox(&mut self, out: &mut String, checked: bool) -> bool {
+        out.push_str(if checked { "[done] " } else { "[todo] " });
+        true
+    }
+}
+
+#[test]
+fn handler_overrides_nodes() {
+    let md = "## Getting Started\n\n```mermaid\ngraph TD\n```\n\n```rs\nx\n```\n\n[a *b*](/u) ![c *d*](i.png)\n\n- [x] done\n- [ ] todo";
+    let expected = "<h2 id=\"getting-started\">Getting Started</h2>\n\
+        <div class=\"mermaid\">graph TD</div>\n\
+        <pre><code class=\"language-rs\">x\n</code></pre>\n\
+        <p><a class=\"ext\" href=\"/u\">a <em>b</em> &#8599;</a> \
+        <figure><img src=\"i.png\"><figcaption>c d</figcaption></figure></p>\n\
+        <ul>\n<li>[done] done</li>\n<li>[todo] todo</li>\n</ul>\n";
+    let opts = ParseOptions {
+        hard_breaks: false,
+        ..Default::default()
+    };
+    assert_eq!(parse_with_handler(md, &opts, &mut Overrides), expected);
+
+    let (ast, refs) = parse_to_ast_with_refs(md, &opts);
+    assert_eq!(
+        render_html_with_handler(&ast, &refs, &opts, &mut Overrides),
+        expected
+    );
+    assert_eq!(
+        parse_with_handler("[a](</x\"y>)", &opts, &mut Overrides),
+        "<p><a class=\"ext\" href=\"/x&quot;y\">a &#8599;</a></p>\n"
+    );
+}
+
+#[test]
+fn default_handler_output_is_unchanged() {
+    struct Noop;
+    impl HtmlHandler for Noop {}
+    let md = "# h\n\n[l](/x \"t\") ![i](y)\n\n- [ ] t\n\n```c\nz\n```";
+    let opts = ParseOptions::default();
+    assert_eq!(parse_with_handler(md, &opts, &mut Noop), parse(md, &opts));
+}
+
+#[test]
+fn code_highlighter_replaces_block_contents() {
+    let opts = ParseOptions {
+        code_highlighter: Some(Box::new(|lang: &str, info: &str, code: &str| {
+            (lang == "rust").then(|| format!("<b data-info=\"{info}\">{}</b>", code.trim_end()))
+        })),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "```rust title=x\nfn a() {}\n```\n\n```py\n<x>\n```\n\n    <y>",
+            &opts
+        ),
+        "<pre><code class=\"language-rust\"><b data-info=\"rust title=x\">fn a() {}</b></code></pre>\n\
+         <pre><code class=\"language-py\">&lt;x&gt;\n</code></pre>\n\
+         <pre><code>&lt;y&gt;\n</code></pre>\n",
+    );
+}
+
+#[test]
+fn parse_options_are_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ParseOptions>();
+}
+
+#[test]
+fn heading_ids_and_anchors() {
+    let md = "# Hello, World!\n\nHello world\n---\n\n### `x` & <b>y</b>\n\n# !!!";
+    let ids = ParseOptions {
+        heading_ids: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(md, &ids),
+        "<h1 id=\"hello-world\">Hello, World!</h1>\n\
+         <h2 id=\"hello-world-1\">Hello world</h2>\n\
+         <h3 id=\"x--y\"><code>x</code> &amp; <b>y</b></h3>\n\
+         <h1>!!!</h1>\n",
+    );
+    let anchors = ParseOptions {
+        heading_anchors: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("## Über uns", &anchors),
+        "<h2 id=\"über-uns\"><a class=\"anchor\" href=\"#%C3%BCber-uns\" aria-hidden=\"true\"></a>Über uns</h2>\n",
+    );
+    assert_eq!(parse("# a", &ParseOptions::default()), "<h1>a</h1>\n");
+}
+
+#[test]
+fn toc_placeholder_is_replaced() {
+    let opts = ParseOptions {
+        toc_placeholder: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("[[toc]]\n\n# A\n\n## [B][]\n\n[TOC]\n\n[b]: /b", &opts),
+        "<nav class=\"toc\">\n<ul>\n<li><a href=\"#a\">A</a>\n<ul>\n<li><a href=\"#b\">B</a></li>\n</ul>\n</li>\n</ul>\n</nav>\n\
+         <h1 id=\"a\">A</h1>\n\
+         <h2 id=\"b\"><a href=\"/b\">B</a></h2>\n\
+         <nav class=\"toc\">\n<ul>\n<li><a href=\"#a\">A</a>\n<ul>\n<li><a href=\"#b\">B</a></li>\n</ul>\n</li>\n</ul>\n</nav>\n",
+    );
+    assert_eq!(
+        parse("[[toc]]", &ParseOptions::default()),
+        "<p>[[toc]]</p>\n"
+    );
+}
+
+#[test]
+fn raw_html_modes() {
+    let md = "<div onclick=\"x()\">\n*a*\n</div>\n\nhi <script>x</script> **b**\n\n- <img src=x onerror=y>";
+    let render = |raw_html| {
+        parse(
+            md,
+            &ParseOptions {
+                raw_html,
+                ..Default::default()
+            },
+        )
+    };
+    assert_eq!(
+        render(RawHtmlMode::PassThrough),
+        "<div onclick=\"x()\">\n*a*\n</div>\n\
+         <p>hi <script>x</script> <strong>b</strong></p>\n\
+         <ul>\n<li>\n<img src=x onerror=y>\n</li>\n</ul>\n",
+    );
+    assert_eq!(
+        render(RawHtmlMode::Escape),
+        "<p>&lt;div onclick=&quot;x()&quot;&gt;\n*a*\n&lt;/div&gt;</p>\n\
+         <p>hi &lt;script&gt;x&lt;/script&gt; <strong>b</strong></p>\n\
+         <ul>\n<li>\n<p>&lt;img src=x onerror=y&gt;</p>\n</li>\n</ul>\n",
+    );
+    assert_eq!(
+        render(RawHtmlMode::Omit),
+        "<p>hi x <strong>b</strong></p>\n<ul>\n<li>\n</li>\n</ul>\n",
+    );
+}
+
+#[test]
+fn safe_urls_neutralize_unsafe_schemes() {
+    let md = "[a](javascript:alert(1)) [b][r] <vbscript:x> ![c](data:image/png;base64,AA) \
+              [d](data:image/png;base64,AA) [e](&#106;avascript:x) <x@y.z> https://ok.dev [f](/rel)\n\n\
+              [r]: JAVASCRIPT:x";
+    let opts = ParseOptions {
+        safe_urls: true,
+        hard_breaks: false,
+        ..Default::default()
+    };
+    let expected = "<p><a href=\"\">a</a> <a href=\"\">b</a> <a href=\"\">vbscript:x</a> \
+                    <img src=\"data:image/png;base64,AA\" alt=\"c\" /> <a href=\"\">d</a> \
+                    <a href=\"\">e</a> <a href=\"mailto:x@y.z\">x@y.z</a> \
+                    <a href=\"https://ok.dev\">https://ok.dev</a> <a href=\"/rel\">f</a></p>\n";
+    assert_eq!(parse(md, &opts), expected);
+    let (ast, refs) = parse_to_ast_with_refs(md, &opts);
+    assert_eq!(render_html_with_refs(&ast, &refs, &opts), expected);
+
+    let custom = ParseOptions {
+        safe_urls: true,
+        allowed_url_schemes: vec!["ipfs".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("[a](IPFS://x) [b](https://y)", &custom),
+        "<p><a href=\"IPFS://x\">a</a> <a href=\"\">b</a></p>\n"
+    );
+}
+
+#[test]
+fn tagfilter_escapes_disallowed_raw_html() {
+    let opts = ParseOptions {
+        tagfilter: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "<strong> <title> <style> <em>\n\n<blockquote>\n  <xmp> is disallowed.  <XMP> is also disallowed.\n</blockquote>",
+            &opts
+        ),
+        "<p><strong> &lt;title> &lt;style> <em></p>\n\
+         <blockquote>\n  &lt;xmp> is disallowed.  &lt;XMP> is also disallowed.\n</blockquote>\n",
+    );
+    assert_eq!(
+        parse("<script>alert(1)</script>", &opts),
+        "&lt;script>alert(1)&lt;/script>\n"
+    );
+    assert_eq!(
+        parse("<script>alert(1)</script>", &ParseOptions::default()),
+        "<script>alert(1)</script>\n"
+    );
+}
+
+#[test]
+fn sanitize_keeps_allowlisted_html() {
+    let opts = ParseOptions {
+        raw_html: RawHtmlMode::Sanitize,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "<details open><summary>More</summary>\n<script>alert(1)</script><style>p{}</style>\n<p style=\"x\" onclick='y()' class=a>1 < 2</p><!-- note -->\n</details>",
+            &opts
+        ),
+        "<details open><summary>More</summary>\n\n<p>1 &lt; 2</p>\n</details>\n",
+    );
+    assert_eq!(
+        parse(
+            "Press <kbd>Ctrl</kbd>, x<sup>2</sup> <img SRC=\"data:image/png;base64,AA\" alt=\"a &amp; b\"/> \
+             <a href=\"&#106;avascript:x\" title=t>l</a> <a href=/ok>m</a> <iframe src=x></iframe> <b onmouseover=x>b</b>",
+            &opts
+        ),
+        "<p>Press <kbd>Ctrl</kbd>, x<sup>2</sup> <img src=\"data:image/png;base64,AA\" alt=\"a &amp; b\" /> \
+         <a title=\"t\">l</a> <a href=\"/ok\">m</a>  <b>b</b></p>\n",
+    );
+
+    let mut allowlist = HtmlAllowlist::new();
+    allowlist.allow("SPAN", &["Class"]);
+    let custom = ParseOptions {
+        raw_html: RawHtmlMode::Sanitize,
+        html_allowlist: Some(allowlist),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("<span class=\"x\" id=y>a</span> <b>b</b>", &custom),
+        "<p><span class=\"x\">a</span> b</p>\n"
+    );
+    assert_eq!(parse("<div onclick=x>\n\n<x-y>", &custom), "");
+}
+
+#[test]
+fn sanitize_balances_close_tags() {
+    let opts = ParseOptions {
+        raw_html: RawHtmlMode::Sanitize,
+        ..Default::default()
+    };
+    // Unmatched close tags cannot end elements of the surrounding page.
+    assert_eq!(
+        parse("</div></div>\n\ntext </span>", &opts),
+        "<p>text </p>\n"
+    );
+    // Matches may span blocks; elements left open are closed at the end.
+    assert_eq!(
+        parse("<div>\n\n*a* <b>b\n\n</div>\n\n<section>", &opts),
+        "<div>\n<p><em>a</em> <b>b</p>\n</b></div>\n"
+    );
+    assert_eq!(
+        parse("<ul><li>x<br></ul>\n\n<p>y", &opts),
+        "<ul><li>x<br></li></ul>\n<p>y\n</p>"
+    );
+}
+
+#[test]
+fn resource_limits_degrade_or_fail() {
+    let nesting = ParseOptions {
+        max_nesting_depth: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("> - > deep\n\n- - ok", &nesting),
+        "<blockquote>\n<ul>\n<li>&gt; deep</li>\n</ul>\n</blockquote>\n\
+         <ul>\n<li>\n<ul>\n<li>ok</li>\n</ul>\n</li>\n</ul>\n",
+    );
+    assert_eq!(
+        try_parse("> - > deep", &nesting),
+        Err(ParseError::NestingTooDeep { limit: 2 })
+    );
+    assert!(try_parse("> - ok", &nesting).is_ok());
+
+    let delims = ParseOptions {
+        max_inline_delimiters: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("*a* **b** *c*\n\n[x](/a) [[[y]]]", &delims),
+        "<p><em>a</em> **b** *c*</p>\n<p><a href=\"/a\">x</a> [[[y]]]</p>\n",
+    );
+    assert_eq!(
+        try_parse("*a* **b**", &delims),
+        Err(ParseError::TooManyDelimiters { limit: 2 })
+    );
+    assert_eq!(
+        try_parse("*a* `c` **b**", &delims),
+        Err(ParseError::TooManyDelimiters { limit: 2 })
+    );
+    assert_eq!(
+        try_parse("*a*\n\n**b**", &delims).unwrap(),
+        "<p><em>a</em></p>\n<p><strong>b</strong></p>\n"
+    );
+
+    let input = ParseOptions {
+        max_input_size: Some(7),
+        ..Default::default()
+    };
+    assert_eq!(parse("# a\n\näöü", &input), "<h1>a</h1>\n<p>ä</p>\n");
+    assert_eq!(
+        try_parse("# a\n\näöü", &input),
+        Err(ParseError::InputTooLarge { size: 11, limit: 7 })
+    );
+
+    let output = ParseOptions {
+        max_output_size: Some(45),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("> - one\n>   - two\n>   - three\n\nafter", &output),
+        "<blockquote>\n<ul>\n<li>one\n<ul>\n<li>two</li>\n</ul>\n</li>\n</ul>\n</blockquote>\n",
+    );
+    assert_eq!(
+        try_parse("para one\n\npara two\n\npara three", &output),
+        Err(ParseError::OutputTooLarge { limit: 45 })
+    );
+    assert_eq!(
+        ParseError::OutputTooLarge { limit: 40 }.to_string(),
+        "output exceeds 40 bytes"
+    );
+}
+
+#[test]
+fn footnotes_are_numbered_by_first_reference() {
+    let opts = ParseOptions {
+        enable_footnotes: true,
+        hard_breaks: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "B[^b] A[^A] b again[^b] missing[^x]\n\n[^a]: Alpha.\n\n[^b]: Beta\n    continues.\n\n    > Quoted.\n[^unused]: Never shown.",
+            &opts
+        ),
+        "<p>B<sup class=\"footnote-ref\"><a href=\"#fn-b\" id=\"fnref-b\" data-footnote-ref>1</a></sup> \
+         A<sup class=\"footnote-ref\"><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref>2</a></sup> \
+         b again<sup class=\"footnote-ref\"><a href=\"#fn-b\" id=\"fnref-b-2\" data-footnote-ref>1</a></sup> \
+         missing[^x]</p>\n\
+         <section class=\"footnotes\" data-footnotes>\n<ol>\n\
+         <li id=\"fn-b\">\n<p>Beta\ncontinues.</p>\n<blockquote>\n<p>Quoted.</p>\n</blockquote>\n\
+         <a href=\"#fnref-b\" class=\"footnote-backref\" data-footnote-backref data-footnote-backref-idx=\"1\" aria-label=\"Back to reference 1\">↩</a> \
+         <a href=\"#fnref-b-2\" class=\"footnote-backref\" data-footnote-backref data-footnote-backref-idx=\"1-2\" aria-label=\"Back to reference 1-2\">↩<sup class=\"footnote-ref\">2</sup></a>\n</li>\n\
+         <li id=\"fn-a\">\n<p>Alpha. \
+         <a href=\"#fnref-a\" class=\"footnote-backref\" data-footnote-backref data-footnote-backref-idx=\"2\" aria-label=\"Back to reference 2\">↩</a></p>\n</li>\n\
+         </ol>\n</section>\n",
+    );
+    // References inside footnotes and list items are numbered too.
+    assert_eq!(
+        parse("- x[^1]\n\n[^1]: See[^2].\n[^2]: Done.", &opts),
+        "<ul>\n<li>x<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\" data-footnote-ref>1</a></sup></li>\n</ul>\n\
+         <section class=\"footnotes\" data-footnotes>\n<ol>\n\
+         <li id=\"fn-1\">\n<p>See<sup class=\"footnote-ref\"><a href=\"#fn-2\" id=\"fnref-2\" data-footnote-ref>2</a></sup>. \
+         <a href=\"#fnref-1\" class=\"footnote-backref\" data-footnote-backref data-footnote-backref-idx=\"1\" aria-label=\"Back to reference 1\">↩</a></p>\n</li>\n\
+         <li id=\"fn-2\">\n<p>Done. \
+         <a href=\"#fnref-2\" class=\"footnote-backref\" data-footnote-backref data-footnote-backref-idx=\"2\" aria-label=\"Back to reference 2\">↩</a></p>\n</li>\n\
+         </ol>\n</section>\n",
+    );
+
+    // Off by default.
+    assert_html("x[^1]\n\n[^1]: /url", "<p>x<a href=\"/url\">^1</a></p>\n");
+}
+
+#[test]
+fn single_item_list_parses_inlines() {
+    assert_html("- *a*", "<ul>\n<li><em>a</em></li>\n</ul>\n");
+    assert_html(
+        "1. __b__ `c` \\*d\\* [e](/f)",
+        "<ol>\n<li><strong>b</strong> <code>c</code> *d* <a href=\"/f\">e</a></li>\n</ol>\n",
+    );
+}
+
+#[test]
+fn single_item_nested_list_parses_inlines() {
+    assert_html(
+        "- a\n  - *b* [c](/d)",
+        "<ul>\n<li>a\n<ul>\n<li><em>b</em> <a href=\"/d\">c</a></li>\n</ul>\n</li>\n</ul>\n",
+    );
+}
+
+#[test]
+fn image_alt_text_is_escaped() {
+    let opts = ParseOptions {
+        enable_math: true,
+        raw_html: RawHtmlMode::Sanitize,
+        safe_urls: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("![$a\" onerror=\"alert(1)$](x.png)", &opts),
+        "<p><img src=\"x.png\" alt=\"a&quot; onerror=&quot;alert(1)\" /></p>\n"
+    );
+    assert_html(
+        "![`a\" onerror=\"x<b>`](x.png)",
+        "<p><img src=\"x.png\" alt=\"a&quot; onerror=&quot;x&lt;b&gt;\" /></p>\n",
+    );
+    assert_html(
+        "![say \"hi\" & 2 > 1](x.png)",
+        "<p><img src=\"x.png\" alt=\"say &quot;hi&quot; &amp; 2 &gt; 1\" /></p>\n",
+    );
+}
+
+#[test]
+fn math_content_is_kept_raw() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_math: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "$a_1 * b_2$, $`x$y`$ and $$\\sum_i x_i$$ cost $5 and $10.\n\n$$\nx < *y*\n$$\n\n```math\nE = mc^2\n```",
+            &opts
+        ),
+        "<p><span class=\"math inline\">a_1 * b_2</span>, <span class=\"math inline\">x$y</span> \
+         and <span class=\"math display\">\\sum_i x_i</span> cost $5 and $10.</p>\n\
+         <div class=\"math display\">x &lt; *y*</div>\n\
+         <div class=\"math display\">E = mc^2</div>\n",
+    );
+    assert_eq!(
+        parse("$a_1 * b_2$\n\n$$\nx\n$$", &ParseOptions::default()),
+        "<p>$a_1 * b_2$</p>\n<p>$$<br />\nx<br />\n$$</p>\n"
+    );
+
+    struct Katex;
+    impl HtmlHandler for Katex {
+        fn math(&mut self, out: &mut String, literal: &str, display: bool) -> bool {
+            out.push_str(&format!("<katex display={display}>{literal}</katex>"));
+            true
+        }
+    }
+    assert_eq!(
+        parse_with_handler("$x$\n\n$$ y $$", &opts, &mut Katex),
+        "<p><katex display=false>x</katex></p>\n<katex display=true>y</katex>"
+    );
+}
+
+#[test]
+fn github_alerts() {
+    let opts = ParseOptions {
+        enable_alerts: true,
+        hard_breaks: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "> [!note]\n> Read *this*.\n\n> [!WARNING]\n> - one\n> - two",
+            &opts
+        ),
+        "<div class=\"markdown-alert markdown-alert-note\">\n\
+         <p class=\"markdown-alert-title\">Note</p>\n\
+         <p>Read <em>this</em>.</p>\n</div>\n\
+         <div class=\"markdown-alert markdown-alert-warning\">\n\
+         <p class=\"markdown-alert-title\">Warning</p>\n\
+         <ul>\n<li>one</li>\n<li>two</li>\n</ul>\n</div>\n",
+    );
+    // Text after the marker, unknown kinds and nested quotes stay plain.
+    assert_eq!(
+        parse(
+            "> [!NOTE] inline\n\n> [!INFO]\n> x\n\n> > [!TIP]\n> > y",
+            &opts
+        ),
+        "<blockquote>\n<p>[!NOTE] inline</p>\n</blockquote>\n\
+         <blockquote>\n<p>[!INFO]\nx</p>\n</blockquote>\n\
+         <blockquote>\n<blockquote>\n<p>[!TIP]\ny</p>\n</blockquote>\n</blockquote>\n",
+    );
+    // Off by default.
+    assert_html(
+        "> [!TIP]\n> y",
+        "<blockquote>\n<p>[!TIP]\ny</p>\n</blockquote>\n",
+    );
+}
+
+#[test]
+fn containers_wrap_blocks() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_containers: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "para\n:::: warning Be careful\n- item\n\n::: inner\nx\n:::\n```\n:::\n```\n::::\nafter",
+            &opts
+        ),
+        "<p>para</p>\n<div class=\"warning\">\n<ul>\n<li>item</li>\n</ul>\n\
+         <div class=\"inner\">\n<p>x</p>\n</div>\n\
+         <pre><code>:::\n</code></pre>\n</div>\n<p>after</p>\n"
+    );
+    // A fence without a name is not a container.
+    assert_eq!(parse(":::\nx\n:::", &opts), "<p>:::\nx\n:::</p>\n");
+    assert_eq!(
+        parse(":::note\nx\n:::", &ParseOptions::default()),
+        "<p>:::note<br />\nx<br />\n:::</p>\n"
+    );
+
+    struct Details;
+    impl HtmlHandler for Details {
+        fn container_open(&mut self, out: &mut String, name: &str, attributes: &str) -> bool {
+            if name != "details" {
+                return false;
+            }
+            out.push_str(&format!("<details>\n<summary>{attributes}</summary>\n"));
+            true
+        }
+        fn container_close(&mut self, out: &mut String, name: &str) -> bool {
+            if name != "details" {
+                return false;
+            }
+            out.push_str("</details>\n");
+            true
+        }
+    }
+    assert_eq!(
+        parse_with_handler(
+            "::: details More\nHidden.\n:::\n\n::: tip\n:::",
+            &opts,
+            &mut Details
+        ),
+        "<details>\n<summary>More</summary>\n<p>Hidden.</p>\n</details>\n\
+         <div class=\"tip\">\n</div>\n"
+    );
+}
+
+#[test]
+fn definition_lists() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_definition_lists: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "Apple\n: A fruit\ncontinued.\n: A company.\n\nOrange\n: Citrus\n  - sweet\n  - round",
+            &opts
+        ),
+        "<dl>\n<dt>Apple</dt>\n<dd>A fruit\ncontinued.</dd>\n<dd>A company.</dd>\n\
+         <dt>Orange</dt>\n<dd>Citrus\n<ul>\n<li>sweet</li>\n<li>round</li>\n</ul>\n</dd>\n</dl>\n"
+    );
+    assert_eq!(
+        parse("Term\n\n: One\n\n  Two\n\n: Three", &opts),
+        "<dl>\n<dt>Term</dt>\n<dd>\n<p>One</p>\n<p>Two</p>\n</dd>\n<dd>\n<p>Three</p>\n</dd>\n</dl>\n"
+    );
+    assert_eq!(parse(": no term", &opts), "<p>: no term</p>\n");
+    assert_eq!(
+        parse("Term\n: def", &ParseOptions::default()),
+        "<p>Term<br />\n: def</p>\n"
+    );
+}
+
+#[test]
+fn attributes_on_headings_code_and_images() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_attributes: true,
+        heading_ids: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "## Install {#setup .tab}\n\nTitle {.x data-n=\"1 2\"}\n=====\n\n# Install",
+            &opts
+        ),
+        "<h2 id=\"setup\" class=\"tab\">Install</h2>\n\
+         <h1 id=\"title\" class=\"x\" data-n=\"1 2\">Title</h1>\n\
+         <h1 id=\"install\">Install</h1>\n"
+    );
+    assert_eq!(
+        parse(
+            "```rust {#ex .numbered}\nx\n```\n\n```{.py .big}\ny\n```",
+            &opts
+        ),
+        "<pre id=\"ex\" class=\"numbered\"><code class=\"language-rust\">x\n</code></pre>\n\
+         <pre class=\"big\"><code class=\"language-py\">y\n</code></pre>\n"
+    );
+    assert_eq!(
+        parse("![logo](logo.png){width=120 .round title='<b>'}", &opts),
+        "<p><img src=\"logo.png\" alt=\"logo\" class=\"round\" width=\"120\" /></p>\n"
+    );
+    assert_eq!(
+        parse("# set{#x}\n\n# a {#b} c\n\n[a]{#x}", &opts),
+        "<h1 id=\"setx\">set{#x}</h1>\n<h1 id=\"a-b-c\">a {#b} c</h1>\n<p>[a]{#x}</p>\n"
+    );
+    assert_eq!(
+        parse("# Install {#setup}", &ParseOptions::default()),
+        "<h1>Install {#setup}</h1>\n"
+    );
+    let escaped = ParseOptions {
+        raw_html: RawHtmlMode::Escape,
+        ..opts
+    };
+    assert_eq!(
+        parse("# a {onclick=\"x()\" title=\"<&>\"}", &escaped),
+        "<h1 id=\"a\" title=\"&lt;&amp;&gt;\">a</h1>\n"
+    );
+}
+
+#[test]
+fn explicit_heading_ids_do_not_collide() {
+    let opts = ParseOptions {
+        enable_attributes: true,
+        heading_ids: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("## Custom\n\n# Intro {#custom}\n\n## Custom", &opts),
+        "<h2 id=\"custom-1\">Custom</h2>\n<h1 id=\"custom\">Intro</h1>\n<h2 id=\"custom-2\">Custom</h2>\n"
+    );
+    let explicit_only = ParseOptions {
+        enable_attributes: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("# A {#a}\n\n# B {#a}", &explicit_only),
+        "<h1 id=\"a\">A</h1>\n<h1 id=\"a-1\">B</h1>\n"
+    );
+}
+
+#[test]
+fn attributes_are_filtered_for_untrusted_input() {
+    let opts = ParseOptions {
+        enable_attributes: true,
+        raw_html: RawHtmlMode::Sanitize,
+        safe_urls: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("# T {style=\"position:fixed;top:0\" .x}", &opts),
+        "<h1 class=\"x\">T</h1>\n"
+    );
+    assert_eq!(
+        parse(
+            "![i](x.png){srcset=\"a.png 1x, javascript:x 2x\" href=javascript:alert(1) data-a=1}",
+            &opts
+        ),
+        "<p><img src=\"x.png\" alt=\"i\" data-a=\"1\" /></p>\n"
+    );
+    assert_eq!(
+        parse(
+            "![i](x.png){srcset=\"a.png 1x, /b.png 2x\" href=https://x.dev}",
+            &opts
+        ),
+        "<p><img src=\"x.png\" alt=\"i\" href=\"https://x.dev\" srcset=\"a.png 1x, /b.png 2x\" /></p>\n"
+    );
+    let trusted = ParseOptions {
+        enable_attributes: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("# T {style=\"color:red\" href=javascript:x}", &trusted),
+        "<h1 href=\"javascript:x\" style=\"color:red\">T</h1>\n"
+    );
+    // `safe_urls` alone also rules out scripts in attributes.
+    let safe_urls = ParseOptions {
+        enable_attributes: true,
+        safe_urls: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "![i](x.png){onerror=alert(1) style=\"x\" width=9}",
+            &safe_urls
+        ),
+        "<p><img src=\"x.png\" alt=\"i\" width=\"9\" /></p>\n"
+    );
+}
+
+#[test]
+fn smart_punctuation() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        smart_punctuation: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(
+            "\"'Shelob' is my name.\"\nWe'll see in the '70s--or later---maybe...",
+            &opts
+        ),
+        "<p>“‘Shelob’ is my name.”\nWe’ll see in the ’70s–or later—maybe…</p>\n"
+    );
+    assert_eq!(
+        parse("five----- *\"emph\"* [\"a\"](/u--v \"t--\")", &opts),
+        "<p>five—– <em>“emph”</em> <a href=\"/u--v\" title=\"t--\">“a”</a></p>\n"
+    );
+    assert_eq!(
+        parse(
+            "`\"a\"--` <b title=\"x\"> https://x.dev/a--b \\\"no\\\" \\-- No\\...",
+            &opts
+        ),
+        "<p><code>&quot;a&quot;--</code> <b title=\"x\"> \
+         <a href=\"https://x.dev/a--b\">https://x.dev/a--b</a> &quot;no&quot; -- No...</p>\n"
+    );
+    assert_eq!(
+        parse("- it's -- tight\n- a", &opts),
+        "<ul>\n<li>it’s – tight</li>\n<li>a</li>\n</ul>\n"
+    );
+    assert_html("\"a\" -- b...", "<p>&quot;a&quot; -- b...</p>\n");
+}
+
+#[test]
+fn smart_punctuation_before_email_autolink() {
+    let opts = ParseOptions {
+        smart_punctuation: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("a--b@x.com x...y@x.com o'neil@x.com", &opts),
+        "<p>a–<a href=\"mailto:b@x.com\">b@x.com</a> x…<a href=\"mailto:y@x.com\">y@x.com</a> \
+         o’<a href=\"mailto:neil@x.com\">neil@x.com</a></p>\n"
+    );
+}
+
+#[test]
+fn emoji_shortcodes() {
+    let opts = ParseOptions {
+        hard_breaks: false,
+        enable_emoji: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse("Shipped :tada::+1: *:heart:* :-1:.", &opts),
+        "<p>Shipped \u{1F389}\u{1F44D} <em>\u{2764}\u{FE0F}</em> \u{1F44E}.</p>\n"
+    );
+    assert_eq!(
+        parse(
+            "`:tada:` a:tada: :tada:b 10:30:00 :nope: :smile_cat: \\:tada:",
+            &opts
+        ),
+        "<p><code>:tada:</code> a:tada: :tada:b 10:30:00 :nope: \u{1F638} :tada:</p>\n"
+    );
+    assert_eq!(
+        parse("- done :white_check_mark:\n- b", &opts),
+        "<ul>\n<li>done \u{2705}</li>\n<li>b</li>\n</ul>\n"
+    );
+    assert_html(":tada:", "<p>:tada:</p>\n");
+}
+
+#[test]
+fn custom_emoji() {
+    let opts = ParseOptions {
+        enable_emoji: true,
+        custom_emoji: Some(Box::new(|name: &str| {
+            matches!(name, "shipit" | "tada").then(|| format!("/e/{name}.png?a&b"))
+        })),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(":shipit: :tada: ![x :shipit:](i.png)", &opts),
+        "<p><img class=\"emoji\" src=\"/e/shipit.png?a&amp;b\" alt=\":shipit:\" /> \u{1F389} \
+         <img src=\"i.png\" alt=\"x :shipit:\" /></p>\n"
+    );
+}
+
+#[test]
+fn front_matter_is_split_off() {
+    let md = "---\ntitle: Notes\ntags: [a, b]\n---\nBody\n";
+    let out = parse_with_front_matter(md, &ParseOptions::default());
+    assert_eq!(
+        out.front_matter,
+        Some(FrontMatter {
+            kind: FrontMatterKind::Yaml,
+            raw: "title: Notes\ntags: [a, b]\n".to_string(),
+        })
+    );
+    assert_eq!(out.body, "<p>Body</p>\n");
+    let opts = ParseOptions {
+        front_matter: true,
+        ..Default::default()
+    };
+    assert_eq!(parse(md, &opts), "<p>Body</p>\n");
+    assert_eq!(
+        parse(md, &ParseOptions::default()),
+        "<hr />\n<h2>title: Notes<br />\ntags: [a, b]</h2>\n<p>Body</p>\n"
+    );
+    // Only at the very start, and only when closed.
+    for md in ["\n---\na: 1\n---\n", "---\na: 1\n"] {
+        let out = parse_with_front_matter(md, &opts);
+        assert_eq!(out.front_matter, None);
+        assert_eq!(out.body, parse(md, &ParseOptions::default()));
+    }
+}
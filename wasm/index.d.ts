@@ -9,12 +9,67 @@ export interface ParseOptions {
   enableStrikethrough?: boolean;
   /** Enable ++underline++ syntax for `<u>`. Default: true. */
   enableUnderline?: boolean;
+  /** Enable ^superscript^ syntax for `<sup>`. Default: false. */
+  enableSuperscript?: boolean;
+  /** Enable ~subscript~ syntax for `<sub>`; `~~double~~` stays strikethrough. Default: false. */
+  enableSubscript?: boolean;
   /** Enable pipe table syntax. Default: true. */
   enableTables?: boolean;
   /** Automatically detect bare URLs and emails and wrap them in links. Default: true. */
   enableAutolink?: boolean;
   /** Enable GitHub-style task lists (`- [ ] unchecked`, `- [x] checked`). Default: true. */
   enableTaskLists?: boolean;
+  /** Enable footnotes (`[^1]` references and `[^1]: text` definitions). Default: false. */
+  enableFootnotes?: boolean;
+  /** Recognize `$inline$`, `$$display$$` and ```` ```math ```` math, kept raw for a client-side renderer. Default: false. */
+  enableMath?: boolean;
+  /** Render `> [!NOTE]`, `[!TIP]`, `[!IMPORTANT]`, `[!WARNING]` and `[!CAUTION]` block quotes as GitHub alerts. Default: false. */
+  enableAlerts?: boolean;
+  /** Parse `:::name` … `:::` containers into `<div class="name">`. Default: false. */
+  enableContainers?: boolean;
+  /** Parse `Term` / `: definition` pairs into `<dl>` definition lists. Default: false. */
+  enableDefinitionLists?: boolean;
+  /** Parse `{#id .class key=value}` attribute blocks after headings, fenced code info strings and images. Default: false. */
+  enableAttributes?: boolean;
+  /** Curly quotes, `--`/`---` en/em dashes and `...` ellipses, like cmark's `--smart`. Default: false. */
+  smartPunctuation?: boolean;
+  /** Replace `:shortcode:` emoji such as `:tada:` and `:+1:` with Unicode characters. Default: false. */
+  enableEmoji?: boolean;
+  /** Leave a leading `---` YAML or `+++` TOML front matter block out of the output. Default: false. */
+  frontMatter?: boolean;
+  /** Record source positions (`pos`) on every block node returned by `parseToAst()`. Default: false. */
+  sourcePositions?: boolean;
+  /** Add `data-sourcepos` attributes to block-level elements in `parse()` output. Default: false. */
+  sourcepos?: boolean;
+  /** Add GitHub-style slug `id` attributes to headings (also set as `id` in the AST). Default: false. */
+  headingIds?: boolean;
+  /** Add an `<a class="anchor">` self-link to every heading. Implies `headingIds`. Default: false. */
+  headingAnchors?: boolean;
+  /** Replace a `[[toc]]` / `[TOC]` paragraph with a table of contents. Implies `headingIds`. Default: false. */
+  tocPlaceholder?: boolean;
+  /**
+   * How raw HTML is rendered: `"passthrough"` writes it unchanged, `"escape"` shows it as text,
+   * `"omit"` drops it and `"sanitize"` keeps a safe subset of tags and attributes. Use anything but
+   * `"passthrough"` for untrusted input. Default: `"passthrough"`.
+   */
+  rawHtml?: "passthrough" | "escape" | "omit" | "sanitize";
+  /** Escape GFM-disallowed raw HTML tags (`<script>`, `<style>`, `<iframe>`, …) while keeping other HTML. Default: false. */
+  tagfilter?: boolean;
+  /** Blank out link and image URLs whose scheme is not in `allowedUrlSchemes` (e.g. `javascript:`). Default: false. */
+  safeUrls?: boolean;
+  /**
+   * Schemes accepted when `safeUrls` is on. Relative URLs are always accepted, images also accept
+   * `data:image/*`. Default: `["http", "https", "mailto", "tel", "ftp"]`.
+   */
+  allowedUrlSchemes?: string[];
+  /** Maximum input size in bytes; anything past it is ignored. Default: unlimited. */
+  maxInputSize?: number;
+  /** Maximum nesting of block quotes and list items; deeper markers stay text. Default: unlimited. */
+  maxNestingDepth?: number;
+  /** Maximum emphasis delimiter runs and brackets per paragraph; further ones stay text. Default: unlimited. */
+  maxInlineDelimiters?: number;
+  /** Maximum HTML output size in bytes; rendering stops before the block that crosses it. Default: unlimited. */
+  maxOutputSize?: number;
 }
 
 /**
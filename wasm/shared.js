@@ -18,9 +18,33 @@ function optionArgs(markdown, options) {
     options?.enableHighlight ?? undefined,
     options?.enableStrikethrough ?? undefined,
     options?.enableUnderline ?? undefined,
+    options?.enableSuperscript ?? undefined,
+    options?.enableSubscript ?? undefined,
     options?.enableTables ?? undefined,
     options?.enableAutolink ?? undefined,
     options?.enableTaskLists ?? undefined,
+    options?.enableFootnotes ?? undefined,
+    options?.enableMath ?? undefined,
+    options?.enableAlerts ?? undefined,
+    options?.enableContainers ?? undefined,
+    options?.enableDefinitionLists ?? undefined,
+    options?.enableAttributes ?? undefined,
+    options?.smartPunctuation ?? undefined,
+    options?.enableEmoji ?? undefined,
+    options?.frontMatter ?? undefined,
+    options?.sourcePositions ?? undefined,
+    options?.sourcepos ?? undefined,
+    options?.headingIds ?? undefined,
+    options?.headingAnchors ?? undefined,
+    options?.tocPlaceholder ?? undefined,
+    options?.rawHtml ?? undefined,
+    options?.tagfilter ?? undefined,
+    options?.safeUrls ?? undefined,
+    options?.allowedUrlSchemes ?? undefined,
+    options?.maxInputSize ?? undefined,
+    options?.maxNestingDepth ?? undefined,
+    options?.maxInlineDelimiters ?? undefined,
+    options?.maxOutputSize ?? undefined,
   ];
 }
 
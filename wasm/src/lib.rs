@@ -1,61 +1,201 @@
-use ironmark::{ParseOptions, parse as ironmark_parse, parse_to_ast as ironmark_parse_to_ast};
+use ironmark::{
+    ParseOptions, RawHtmlMode, parse as ironmark_parse, parse_to_ast as ironmark_parse_to_ast,
+};
 use wasm_bindgen::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 fn build_options(
     hard_breaks: Option<bool>,
     enable_highlight: Option<bool>,
     enable_strikethrough: Option<bool>,
     enable_underline: Option<bool>,
+    enable_superscript: Option<bool>,
+    enable_subscript: Option<bool>,
     enable_tables: Option<bool>,
     enable_autolink: Option<bool>,
     enable_task_lists: Option<bool>,
-) -> ParseOptions {
-    ParseOptions {
+    enable_footnotes: Option<bool>,
+    enable_math: Option<bool>,
+    enable_alerts: Option<bool>,
+    enable_containers: Option<bool>,
+    enable_definition_lists: Option<bool>,
+    enable_attributes: Option<bool>,
+    smart_punctuation: Option<bool>,
+    enable_emoji: Option<bool>,
+    front_matter: Option<bool>,
+    source_positions: Option<bool>,
+    sourcepos: Option<bool>,
+    heading_ids: Option<bool>,
+    heading_anchors: Option<bool>,
+    toc_placeholder: Option<bool>,
+    raw_html: Option<String>,
+    tagfilter: Option<bool>,
+    safe_urls: Option<bool>,
+    allowed_url_schemes: Option<Vec<String>>,
+    max_input_size: Option<usize>,
+    max_nesting_depth: Option<usize>,
+    max_inline_delimiters: Option<usize>,
+    max_output_size: Option<usize>,
+) -> Result<ParseOptions, JsValue> {
+    let raw_html = match raw_html.as_deref() {
+        None | Some("passthrough") => RawHtmlMode::PassThrough,
+        Some("escape") => RawHtmlMode::Escape,
+        Some("omit") => RawHtmlMode::Omit,
+        Some("sanitize") => RawHtmlMode::Sanitize,
+        Some(other) => {
+            return Err(JsValue::from_str(&format!(
+                "invalid rawHtml mode {other:?}, expected \"passthrough\", \"escape\", \"omit\" or \"sanitize\""
+            )));
+        }
+    };
+    Ok(ParseOptions {
         hard_breaks: hard_breaks.unwrap_or(true),
         enable_highlight: enable_highlight.unwrap_or(true),
         enable_strikethrough: enable_strikethrough.unwrap_or(true),
         enable_underline: enable_underline.unwrap_or(true),
+        enable_superscript: enable_superscript.unwrap_or(false),
+        enable_subscript: enable_subscript.unwrap_or(false),
         enable_tables: enable_tables.unwrap_or(true),
         enable_autolink: enable_autolink.unwrap_or(true),
         enable_task_lists: enable_task_lists.unwrap_or(true),
-    }
+        enable_footnotes: enable_footnotes.unwrap_or(false),
+        enable_math: enable_math.unwrap_or(false),
+        enable_alerts: enable_alerts.unwrap_or(false),
+        enable_containers: enable_containers.unwrap_or(false),
+        enable_definition_lists: enable_definition_lists.unwrap_or(false),
+        enable_attributes: enable_attributes.unwrap_or(false),
+        smart_punctuation: smart_punctuation.unwrap_or(false),
+        enable_emoji: enable_emoji.unwrap_or(false),
+        front_matter: front_matter.unwrap_or(false),
+        source_positions: source_positions.unwrap_or(false),
+        sourcepos: sourcepos.unwrap_or(false),
+        heading_ids: heading_ids.unwrap_or(false),
+        heading_anchors: heading_anchors.unwrap_or(false),
+        toc_placeholder: toc_placeholder.unwrap_or(false),
+        raw_html,
+        html_allowlist: None,
+        tagfilter: tagfilter.unwrap_or(false),
+        safe_urls: safe_urls.unwrap_or(false),
+        allowed_url_schemes: allowed_url_schemes
+            .unwrap_or_else(|| ParseOptions::default().allowed_url_schemes),
+        max_input_size,
+        max_nesting_depth,
+        max_inline_delimiters,
+        max_output_size,
+        code_highlighter: None,
+        custom_emoji: None,
+    })
 }
 
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn parse(
     markdown: &str,
     hard_breaks: Option<bool>,
     enable_highlight: Option<bool>,
     enable_strikethrough: Option<bool>,
     enable_underline: Option<bool>,
+    enable_superscript: Option<bool>,
+    enable_subscript: Option<bool>,
     enable_tables: Option<bool>,
     enable_autolink: Option<bool>,
     enable_task_lists: Option<bool>,
-) -> String {
-    ironmark_parse(
+    enable_footnotes: Option<bool>,
+    enable_math: Option<bool>,
+    enable_alerts: Option<bool>,
+    enable_containers: Option<bool>,
+    enable_definition_lists: Option<bool>,
+    enable_attributes: Option<bool>,
+    smart_punctuation: Option<bool>,
+    enable_emoji: Option<bool>,
+    front_matter: Option<bool>,
+    source_positions: Option<bool>,
+    sourcepos: Option<bool>,
+    heading_ids: Option<bool>,
+    heading_anchors: Option<bool>,
+    toc_placeholder: Option<bool>,
+    raw_html: Option<String>,
+    tagfilter: Option<bool>,
+    safe_urls: Option<bool>,
+    allowed_url_schemes: Option<Vec<String>>,
+    max_input_size: Option<usize>,
+    max_nesting_depth: Option<usize>,
+    max_inline_delimiters: Option<usize>,
+    max_output_size: Option<usize>,
+) -> Result<String, JsValue> {
+    Ok(ironmark_parse(
         markdown,
         &build_options(
             hard_breaks,
             enable_highlight,
             enable_strikethrough,
             enable_underline,
+            enable_superscript,
+            enable_subscript,
             enable_tables,
             enable_autolink,
             enable_task_lists,
-        ),
-    )
+            enable_footnotes,
+            enable_math,
+            enable_alerts,
+            enable_containers,
+            enable_definition_lists,
+            enable_attributes,
+            smart_punctuation,
+            enable_emoji,
+            front_matter,
+            source_positions,
+            sourcepos,
+            heading_ids,
+            heading_anchors,
+            toc_placeholder,
+            raw_html,
+            tagfilter,
+            safe_urls,
+            allowed_url_schemes,
+            max_input_size,
+            max_nesting_depth,
+            max_inline_delimiters,
+            max_output_size,
+        )?,
+    ))
 }
 
 #[wasm_bindgen(js_name = "parseToAst")]
+#[allow(clippy::too_many_arguments)]
 pub fn parse_to_ast(
     markdown: &str,
     hard_breaks: Option<bool>,
     enable_highlight: Option<bool>,
     enable_strikethrough: Option<bool>,
     enable_underline: Option<bool>,
+    enable_superscript: Option<bool>,
+    enable_subscript: Option<bool>,
     enable_tables: Option<bool>,
     enable_autolink: Option<bool>,
     enable_task_lists: Option<bool>,
+    enable_footnotes: Option<bool>,
+    enable_math: Option<bool>,
+    enable_alerts: Option<bool>,
+    enable_containers: Option<bool>,
+    enable_definition_lists: Option<bool>,
+    enable_attributes: Option<bool>,
+    smart_punctuation: Option<bool>,
+    enable_emoji: Option<bool>,
+    front_matter: Option<bool>,
+    source_positions: Option<bool>,
+    sourcepos: Option<bool>,
+    heading_ids: Option<bool>,
+    heading_anchors: Option<bool>,
+    toc_placeholder: Option<bool>,
+    raw_html: Option<String>,
+    tagfilter: Option<bool>,
+    safe_urls: Option<bool>,
+    allowed_url_schemes: Option<Vec<String>>,
+    max_input_size: Option<usize>,
+    max_nesting_depth: Option<usize>,
+    max_inline_delimiters: Option<usize>,
+    max_output_size: Option<usize>,
 ) -> Result<String, JsValue> {
     let ast = ironmark_parse_to_ast(
         markdown,
@@ -64,10 +204,34 @@ pub fn parse_to_ast(
             enable_highlight,
             enable_strikethrough,
             enable_underline,
+            enable_superscript,
+            enable_subscript,
             enable_tables,
             enable_autolink,
             enable_task_lists,
-        ),
+            enable_footnotes,
+            enable_math,
+            enable_alerts,
+            enable_containers,
+            enable_definition_lists,
+            enable_attributes,
+            smart_punctuation,
+            enable_emoji,
+            front_matter,
+            source_positions,
+            sourcepos,
+            heading_ids,
+            heading_anchors,
+            toc_placeholder,
+            raw_html,
+            tagfilter,
+            safe_urls,
+            allowed_url_schemes,
+            max_input_size,
+            max_nesting_depth,
+            max_inline_delimiters,
+            max_output_size,
+        )?,
     );
     serde_json::to_string(&ast)
         .map_err(|err| JsValue::from_str(&format!("AST serialization failed: {err}")))